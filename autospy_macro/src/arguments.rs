@@ -3,8 +3,8 @@ use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use std::collections::HashMap;
 use syn::{
-    Expr, FnArg, GenericParam, Generics, Pat, PatType, TraitItemFn, Type, WherePredicate,
-    parse_quote,
+    Expr, FnArg, GenericParam, Generics, Pat, PatType, TraitItemFn, Type, TypeReference,
+    WherePredicate, parse_quote,
 };
 
 #[derive(PartialEq, Eq, Debug)]
@@ -16,15 +16,61 @@ pub struct SpyArgument {
     pub dereference_count: u8,
 }
 
+#[derive(PartialEq, Eq, Debug)]
+pub struct OutArgument {
+    pub name: Ident,
+    pub referenced_type: Type,
+    pub capture_written: bool,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct BorrowedArgument {
+    pub name: Ident,
+    pub borrowed_type: Type,
+    pub borrow: Borrow,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Borrow {
+    Owned,
+    Shared,
+    Mutable,
+}
+
 pub fn spy_arguments(function: &TraitItemFn) -> impl Iterator<Item = SpyArgument> {
     non_self_function_arguments(function)
         .filter_map(|argument| spy_argument(&generics_map(&function.sig.generics), argument))
 }
 
+pub fn out_arguments(function: &TraitItemFn) -> impl Iterator<Item = OutArgument> {
+    non_self_function_arguments(function)
+        .filter(|argument| {
+            is_argument_marked_as_out(argument) || is_argument_marked_as_capture_written(argument)
+        })
+        .map(out_argument)
+}
+
+pub fn borrowed_arguments(function: &TraitItemFn) -> impl Iterator<Item = BorrowedArgument> {
+    let generics_map = generics_map(&function.sig.generics);
+    non_self_function_arguments(function)
+        .map(move |argument| borrowed_argument(&generics_map, argument))
+}
+
 pub fn is_argument_marked_as_ignore(argument: &PatType) -> bool {
     argument.attrs.iter().any(attribute::is_ignore_attribute)
 }
 
+pub fn is_argument_marked_as_out(argument: &PatType) -> bool {
+    argument.attrs.iter().any(attribute::is_out_attribute)
+}
+
+pub fn is_argument_marked_as_capture_written(argument: &PatType) -> bool {
+    argument
+        .attrs
+        .iter()
+        .any(attribute::is_capture_written_attribute)
+}
+
 pub fn generics_map(generics: &Generics) -> HashMap<Ident, TokenStream> {
     parameter_generics(generics)
         .into_iter()
@@ -112,6 +158,81 @@ fn spy_argument(
     })
 }
 
+fn out_argument(argument: &PatType) -> OutArgument {
+    assert!(
+        is_argument_marked_as_out(argument),
+        "capture_written arguments must also be marked as out"
+    );
+
+    let Pat::Ident(pat_ident) = &*argument.pat else {
+        panic!("out arguments must be named")
+    };
+
+    let Type::Reference(TypeReference {
+        mutability: Some(_),
+        elem,
+        ..
+    }) = &*argument.ty
+    else {
+        panic!("out arguments must be mutable references")
+    };
+
+    OutArgument {
+        name: pat_ident.ident.clone(),
+        referenced_type: *elem.clone(),
+        capture_written: is_argument_marked_as_capture_written(argument),
+    }
+}
+
+fn borrowed_argument(
+    generics_map: &HashMap<Ident, TokenStream>,
+    argument: &PatType,
+) -> BorrowedArgument {
+    let Pat::Ident(pat_ident) = &*argument.pat else {
+        panic!("borrowed function arguments must be named")
+    };
+
+    let (borrowed_type, borrow) = match &*argument.ty {
+        Type::Reference(reference) => {
+            let mut reference = reference.clone();
+            *reference.elem = dyn_generic_type(generics_map, &reference.elem);
+            let borrow = if reference.mutability.is_some() {
+                Borrow::Mutable
+            } else {
+                Borrow::Shared
+            };
+            (Type::Reference(reference), borrow)
+        }
+        owned_type => {
+            let referenced_type = dyn_generic_type(generics_map, owned_type);
+            (parse_quote! { &#referenced_type }, Borrow::Owned)
+        }
+    };
+
+    BorrowedArgument {
+        name: pat_ident.ident.clone(),
+        borrowed_type,
+        borrow,
+    }
+}
+
+fn dyn_generic_type(generics_map: &HashMap<Ident, TokenStream>, argument_type: &Type) -> Type {
+    if let Type::ImplTrait(impl_trait) = argument_type {
+        let bounds = &impl_trait.bounds;
+        return parse_quote! { (dyn #bounds) };
+    }
+
+    if let Type::Path(type_path) = argument_type
+        && type_path.qself.is_none()
+        && type_path.path.segments.len() == 1
+        && let Some(bounds) = generics_map.get(&type_path.path.segments[0].ident)
+    {
+        return parse_quote! { (dyn #bounds) };
+    }
+
+    argument_type.clone()
+}
+
 fn remove_references(argument_type: &Type) -> (Type, u8) {
     match argument_type {
         Type::Reference(referenced_argument) => {
@@ -124,7 +245,10 @@ fn remove_references(argument_type: &Type) -> (Type, u8) {
 
 #[cfg(test)]
 mod tests {
-    use super::{SpyArgument, spy_arguments};
+    use super::{
+        Borrow, BorrowedArgument, OutArgument, SpyArgument, borrowed_arguments, out_arguments,
+        spy_arguments,
+    };
     use syn::{TraitItemFn, parse_quote};
 
     #[test]
@@ -263,4 +387,121 @@ mod tests {
 
         assert_eq!(vec![expected], spy_arguments(&input).collect::<Vec<_>>());
     }
+
+    #[test]
+    fn out_attribute_arguments() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, bar: u8, #[autospy(out)] baz: &mut [u8]);
+        };
+
+        let expected = OutArgument {
+            name: parse_quote! { baz },
+            referenced_type: parse_quote! { [u8] },
+            capture_written: false,
+        };
+
+        assert_eq!(vec![expected], out_arguments(&input).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn out_attribute_arguments_marked_with_capture_written() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, #[autospy(out)] #[autospy(capture_written)] bar: &mut String);
+        };
+
+        let expected = OutArgument {
+            name: parse_quote! { bar },
+            referenced_type: parse_quote! { String },
+            capture_written: true,
+        };
+
+        assert_eq!(vec![expected], out_arguments(&input).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "capture_written arguments must also be marked as out")]
+    fn capture_written_attribute_without_out_attribute() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, #[autospy(capture_written)] bar: &mut String);
+        };
+
+        let _ = out_arguments(&input).count();
+    }
+
+    #[test]
+    fn out_attribute_arguments_are_still_spy_arguments() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, #[autospy(out)] bar: &mut String);
+        };
+
+        assert_eq!(1, spy_arguments(&input).count());
+    }
+
+    #[test]
+    #[should_panic(expected = "out arguments must be mutable references")]
+    fn out_attribute_on_shared_reference_argument() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, #[autospy(out)] bar: &[u8]);
+        };
+
+        let _ = out_arguments(&input).count();
+    }
+
+    #[test]
+    fn borrowed_arguments_keep_references_and_borrow_owned_arguments() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo<'a>(&self, bar: &'a str, baz: &mut Vec<u8>, qux: u8);
+        };
+
+        let expected = vec![
+            BorrowedArgument {
+                name: parse_quote! { bar },
+                borrowed_type: parse_quote! { &'a str },
+                borrow: Borrow::Shared,
+            },
+            BorrowedArgument {
+                name: parse_quote! { baz },
+                borrowed_type: parse_quote! { &mut Vec<u8> },
+                borrow: Borrow::Mutable,
+            },
+            BorrowedArgument {
+                name: parse_quote! { qux },
+                borrowed_type: parse_quote! { &u8 },
+                borrow: Borrow::Owned,
+            },
+        ];
+
+        assert_eq!(expected, borrowed_arguments(&input).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn borrowed_generic_arguments_are_dyn() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo<T: Fn(u8)>(&self, bar: &T, baz: impl Fn() + Send);
+        };
+
+        let expected = vec![
+            BorrowedArgument {
+                name: parse_quote! { bar },
+                borrowed_type: parse_quote! { &(dyn Fn(u8)) },
+                borrow: Borrow::Shared,
+            },
+            BorrowedArgument {
+                name: parse_quote! { baz },
+                borrowed_type: parse_quote! { &(dyn Fn() + Send) },
+                borrow: Borrow::Owned,
+            },
+        ];
+
+        assert_eq!(expected, borrowed_arguments(&input).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn borrowed_arguments_include_ignored_arguments() {
+        let input: TraitItemFn = parse_quote! {
+            fn foo(&self, #[autospy(ignore)] bar: &dyn Fn(u8));
+        };
+
+        assert_eq!(1, borrowed_arguments(&input).count());
+    }
 }
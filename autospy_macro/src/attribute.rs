@@ -13,10 +13,26 @@ pub fn is_ignore_attribute(attribute: &Attribute) -> bool {
     autospy_attribute(attribute).is_some_and(|tokens| tokens.to_string() == "ignore")
 }
 
+pub fn is_out_attribute(attribute: &Attribute) -> bool {
+    autospy_attribute(attribute).is_some_and(|tokens| tokens.to_string() == "out")
+}
+
+pub fn is_capture_written_attribute(attribute: &Attribute) -> bool {
+    autospy_attribute(attribute).is_some_and(|tokens| tokens.to_string() == "capture_written")
+}
+
 pub fn has_use_default_attribute(attributes: &[Attribute]) -> bool {
     autospy_attributes(attributes).any(|attribute| attribute.to_string() == "use_default")
 }
 
+pub fn has_record_default_attribute(attributes: &[Attribute]) -> bool {
+    autospy_attributes(attributes).any(|attribute| attribute.to_string() == "record_default")
+}
+
+pub fn has_borrowed_attribute(attributes: &[Attribute]) -> bool {
+    autospy_attributes(attributes).any(|attribute| attribute.to_string() == "borrowed")
+}
+
 pub fn associated_type(attributes: &[Attribute]) -> Option<Type> {
     Some(syn::parse2(autospy_attributes(attributes).next()?).expect("invalid associated type"))
 }
@@ -43,6 +59,12 @@ pub fn return_type(attributes: &[Attribute]) -> Option<Type> {
         .map(parse_literal_expression::<Type>)
 }
 
+pub fn default_return(attributes: &[Attribute]) -> Option<Expr> {
+    key_value_autospy_attributes(attributes)
+        .find_map(|name_value| matching_meta_name_value(name_value, "default_return"))
+        .map(parse_literal_expression::<Expr>)
+}
+
 fn matching_meta_name_value(name_value: MetaNameValue, expected_path: &str) -> Option<Expr> {
     match name_value {
         MetaNameValue { path, value, .. } if path.is_ident(expected_path) => Some(value),
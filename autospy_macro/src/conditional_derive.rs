@@ -0,0 +1,164 @@
+use crate::inspect::cfg;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Generics;
+
+pub struct Variant {
+    pub name: Ident,
+    pub field_type: Option<TokenStream>,
+}
+
+impl Variant {
+    pub fn definition(&self) -> TokenStream {
+        let variant_name = &self.name;
+        self.field_type.as_ref().map_or_else(
+            || quote! { #variant_name },
+            |field_type| quote! { #variant_name(#field_type) },
+        )
+    }
+}
+
+pub fn enum_impls(name: &Ident, generics: &Generics, variants: &[Variant]) -> TokenStream {
+    let field_types: Vec<_> = variants
+        .iter()
+        .filter_map(|variant| variant.field_type.clone())
+        .collect();
+
+    let clone_arms = variants.iter().map(clone_arm);
+    let debug_arms = variants.iter().map(debug_arm);
+    let partial_eq_arms = variants.iter().map(partial_eq_arm);
+
+    if variants.is_empty() {
+        let empty_body = quote! { match *self {} };
+        return impls(
+            name,
+            generics,
+            &field_types,
+            &empty_body,
+            &empty_body,
+            &empty_body,
+        );
+    }
+
+    impls(
+        name,
+        generics,
+        &field_types,
+        &quote! { match self { #(#clone_arms,)* } },
+        &quote! { match self { #(#debug_arms,)* } },
+        &quote! {
+            #[allow(unreachable_patterns)]
+            match (self, other) {
+                #(#partial_eq_arms,)*
+                _ => false,
+            }
+        },
+    )
+}
+
+pub fn struct_impls(
+    name: &Ident,
+    generics: &Generics,
+    field_names: &[Ident],
+    field_types: &[TokenStream],
+) -> TokenStream {
+    let name_string = name.to_string();
+    let field_strings = field_names.iter().map(ToString::to_string);
+
+    impls(
+        name,
+        generics,
+        field_types,
+        &quote! { Self { #(#field_names: Clone::clone(&self.#field_names)),* } },
+        &quote! { f.debug_struct(#name_string) #(.field(#field_strings, &self.#field_names))* .finish() },
+        &quote! { #(self.#field_names == other.#field_names)&&* },
+    )
+}
+
+fn clone_arm(variant: &Variant) -> TokenStream {
+    let variant_name = &variant.name;
+    if variant.field_type.is_some() {
+        quote! { Self::#variant_name(field) => Self::#variant_name(Clone::clone(field)) }
+    } else {
+        quote! { Self::#variant_name => Self::#variant_name }
+    }
+}
+
+fn debug_arm(variant: &Variant) -> TokenStream {
+    let variant_name = &variant.name;
+    let variant_string = variant_name.to_string();
+    if variant.field_type.is_some() {
+        quote! { Self::#variant_name(field) => f.debug_tuple(#variant_string).field(field).finish() }
+    } else {
+        quote! { Self::#variant_name => f.write_str(#variant_string) }
+    }
+}
+
+fn partial_eq_arm(variant: &Variant) -> TokenStream {
+    let variant_name = &variant.name;
+    if variant.field_type.is_some() {
+        quote! { (Self::#variant_name(field), Self::#variant_name(other_field)) => field == other_field }
+    } else {
+        quote! { (Self::#variant_name, Self::#variant_name) => true }
+    }
+}
+
+fn impls(
+    name: &Ident,
+    generics: &Generics,
+    field_types: &[TokenStream],
+    clone_body: &TokenStream,
+    debug_body: &TokenStream,
+    partial_eq_body: &TokenStream,
+) -> TokenStream {
+    let cfg = cfg();
+
+    let (impl_generics, type_generics, _) = generics.split_for_impl();
+    let clone_where_clause = where_clause(generics, field_types, &quote! { Clone });
+    let debug_where_clause = where_clause(generics, field_types, &quote! { std::fmt::Debug });
+    let partial_eq_where_clause = where_clause(generics, field_types, &quote! { PartialEq });
+
+    quote! {
+        #cfg
+        impl #impl_generics Clone for #name #type_generics #clone_where_clause {
+            fn clone(&self) -> Self {
+                #clone_body
+            }
+        }
+
+        #cfg
+        impl #impl_generics std::fmt::Debug for #name #type_generics #debug_where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #debug_body
+            }
+        }
+
+        #cfg
+        impl #impl_generics PartialEq for #name #type_generics #partial_eq_where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                #partial_eq_body
+            }
+        }
+    }
+}
+
+// the bounds are higher-ranked so they are never trivially false, the impl just does not apply
+pub fn where_clause(
+    generics: &Generics,
+    field_types: &[TokenStream],
+    bound: &TokenStream,
+) -> TokenStream {
+    let predicates: Vec<_> = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .collect();
+
+    if predicates.is_empty() && field_types.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        where #(#predicates,)* #(for<'autospy> #field_types: #bound),*
+    }
+}
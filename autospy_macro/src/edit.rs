@@ -1,4 +1,4 @@
-use crate::arguments::is_argument_marked_as_ignore;
+use crate::arguments::{is_argument_marked_as_ignore, is_argument_marked_as_out};
 use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
 use syn::visit_mut::VisitMut;
 use syn::{FnArg, PatType, Signature, Type, parse_quote};
@@ -43,7 +43,9 @@ impl AssociatedTypeReplacer<'_> {
 // TODO: do this by visitor pattern too?
 pub fn underscore_ignored_arguments_in_signature(signature: &mut Signature) {
     non_self_signature_arguments_mut(signature)
-        .filter(|argument| is_argument_marked_as_ignore(argument))
+        .filter(|argument| {
+            is_argument_marked_as_ignore(argument) && !is_argument_marked_as_out(argument)
+        })
         .for_each(rename_argument_to_underscore);
 }
 
@@ -1,29 +1,45 @@
 use crate::associated_types::get_associated_types;
+use crate::generate_spy_arguments::generate_spy_arguments;
+use crate::generate_spy_borrowed::generate_spy_borrowed;
+use crate::generate_spy_calls::generate_spy_calls;
 use crate::generate_spy_default::generate_spy_default;
+use crate::generate_spy_impl::generate_spy_impl;
+use crate::generate_spy_serialize::generate_spy_serialize;
 use crate::generate_spy_struct::generate_spy_struct;
 use crate::generate_spy_trait::generate_spy_trait;
+use crate::options::Options;
 use crate::strip_attributes::strip_attributes;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::ItemTrait;
 
-pub fn generate(item_trait: &ItemTrait, external_trait: bool) -> TokenStream {
+pub fn generate(item_trait: &ItemTrait, options: &Options) -> TokenStream {
     let associated_types = get_associated_types(item_trait);
-    let stripped_item_trait = if external_trait {
+    let stripped_item_trait = if options.external {
         TokenStream::new()
     } else {
         let stripped_item_trait = strip_attributes(item_trait.clone());
         quote! { #stripped_item_trait }
     };
-    let spy_struct = generate_spy_struct(item_trait, &associated_types);
-    let spy_default = generate_spy_default(item_trait, &associated_types);
-    let spy_trait = generate_spy_trait(item_trait, &associated_types);
+    let spy_struct = generate_spy_struct(item_trait, &associated_types, options);
+    let spy_default = generate_spy_default(item_trait, &associated_types, options);
+    let spy_trait = generate_spy_trait(item_trait, &associated_types, options);
+    let spy_impl = generate_spy_impl(item_trait, &associated_types, options);
+    let spy_calls = generate_spy_calls(item_trait, &associated_types, options);
+    let spy_arguments = generate_spy_arguments(item_trait, &associated_types, options);
+    let spy_serialize = generate_spy_serialize(item_trait, &associated_types, options);
+    let spy_borrowed = generate_spy_borrowed(item_trait, &associated_types, options);
 
     quote! {
         #stripped_item_trait
         #spy_struct
         #spy_default
         #spy_trait
+        #spy_impl
+        #spy_calls
+        #spy_arguments
+        #spy_serialize
+        #spy_borrowed
     }
 }
 
@@ -38,11 +54,12 @@ pub fn tuple_or_single(mut items: impl Iterator<Item = TokenStream>) -> TokenStr
 #[cfg(test)]
 mod tests {
     use crate::generate::generate;
+    use crate::options::Options;
     use syn::{ItemTrait, parse_quote};
 
     #[allow(clippy::needless_pass_by_value)]
     fn generate_pretty(item_trait: ItemTrait) -> String {
-        let expanded = generate(&item_trait, false).to_string();
+        let expanded = generate(&item_trait, &Options::default()).to_string();
         prettyplease::unparse(&syn::parse_file(&expanded).unwrap())
     }
 
@@ -53,11 +70,128 @@ mod tests {
                 fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize>;
             }
         };
-        let expanded = generate(&item_trait, true).to_string();
+        let expanded = generate(
+            &item_trait,
+            &Options {
+                external: true,
+                ..Options::default()
+            },
+        )
+        .to_string();
 
         insta::assert_snapshot!(prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
     }
 
+    #[test]
+    fn traits_marked_with_calls_record_calls_in_order() {
+        let item_trait = parse_quote! {
+            trait MyTrait {
+                fn open(&self);
+                fn write(&self, id: u8, bytes: &[u8]) -> usize;
+            }
+        };
+        let expanded = generate(
+            &item_trait,
+            &Options {
+                calls: true,
+                ..Options::default()
+            },
+        )
+        .to_string();
+
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
+    }
+
+    #[test]
+    fn traits_marked_with_wrapping_forward_to_inner_implementation() {
+        let item_trait = parse_quote! {
+            trait MyTrait {
+                fn write(&self, id: u8, bytes: &[u8]) -> usize;
+                fn flush(&mut self);
+            }
+        };
+        let expanded = generate(
+            &item_trait,
+            &Options {
+                wrapping: true,
+                ..Options::default()
+            },
+        )
+        .to_string();
+
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
+    }
+
+    #[test]
+    fn nice_traits_use_default_and_declared_return_values() {
+        let item_trait = parse_quote! {
+            trait MyTrait {
+                fn count(&self) -> usize;
+                #[autospy(default_return = "Ok(())")]
+                fn write(&self, bytes: &[u8]) -> std::io::Result<()>;
+            }
+        };
+        let expanded = generate(
+            &item_trait,
+            &Options {
+                nice: true,
+                ..Options::default()
+            },
+        )
+        .to_string();
+
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
+    }
+
+    #[test]
+    fn traits_marked_with_named_arguments_capture_arguments_in_structs() {
+        let item_trait = parse_quote! {
+            trait MyTrait {
+                fn send(&self, topic: &str, retries: u8);
+                fn flush(&self, timeout: u64);
+            }
+        };
+        let expanded = generate(
+            &item_trait,
+            &Options {
+                calls: true,
+                named_arguments: true,
+                ..Options::default()
+            },
+        )
+        .to_string();
+
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
+    }
+
+    #[test]
+    fn arguments_marked_with_out_attribute_can_be_written_by_return_function() {
+        insta::assert_snapshot!(generate_pretty(parse_quote! {
+            trait MyTrait {
+                fn read(&mut self, #[autospy(out)] buf: &mut [u8]) -> std::io::Result<usize>;
+            }
+        }));
+    }
+
+    #[test]
+    fn functions_marked_with_borrowed_attribute_pass_real_arguments_to_return_function() {
+        insta::assert_snapshot!(generate_pretty(parse_quote! {
+            trait MyTrait {
+                #[autospy(borrowed)]
+                fn trim<'a>(&self, text: &'a str) -> &'a str;
+            }
+        }));
+    }
+
+    #[test]
+    fn arguments_containing_nested_references_are_captured_as_owned() {
+        insta::assert_snapshot!(generate_pretty(parse_quote! {
+            trait MyTrait {
+                fn function(&self, name: Option<&str>, paths: &[&Path]);
+            }
+        }));
+    }
+
     #[test]
     fn arguments_marked_with_into_attribute_are_captured_as_that_type() {
         insta::assert_snapshot!(generate_pretty(parse_quote! {
@@ -403,6 +537,19 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn traits_with_recorded_default_definitions() {
+        insta::assert_snapshot!(generate_pretty(parse_quote! {
+            trait TestTrait {
+                fn function(&self, argument: u8) -> u8;
+                #[autospy(record_default)]
+                fn recorded(&self, argument: &str) -> u8 {
+                    self.function(argument.len() as u8)
+                }
+            }
+        }));
+    }
+
     #[test]
     fn traits_with_basic_generics() {
         insta::assert_snapshot!(generate_pretty(parse_quote! {
@@ -0,0 +1,336 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::generate_spy_calls::call_variant;
+use crate::generate_spy_struct::{generate_struct_generics, spy_argument_fields};
+use crate::generics::{generics_idents, generics_used_by};
+use crate::inspect::cfg;
+use crate::options::Options;
+use crate::{arguments, conditional_derive, inspect};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Generics, ItemTrait, TraitItemFn, Type, parse_quote};
+
+pub fn generate_spy_arguments(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    let spy_arguments = inspect::spy_functions(item_trait)
+        .filter(|function| has_named_arguments(function, options))
+        .map(|function| function_as_spy_arguments(item_trait, &function, associated_spy_types));
+
+    quote! {
+        #(#spy_arguments)*
+    }
+}
+
+pub fn has_named_arguments(function: &TraitItemFn, options: &Options) -> bool {
+    options.named_arguments && arguments::spy_arguments(function).nth(1).is_some()
+}
+
+pub fn arguments_name(spy_name: &Ident, function: &TraitItemFn) -> Ident {
+    format_ident!("{}{}Args", spy_name, call_variant(function))
+}
+
+pub fn arguments_type(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> TokenStream {
+    let arguments_name = arguments_name(&format_ident!("{}Spy", item_trait.ident), function);
+    let arguments_generics_idents = generics_idents(
+        &arguments_generics(item_trait, function, associated_spy_types),
+        false,
+    );
+    quote! { #arguments_name #arguments_generics_idents }
+}
+
+fn arguments_generics(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> Generics {
+    let field_types: Vec<Type> = spy_argument_fields(function, associated_spy_types)
+        .into_iter()
+        .map(|field| {
+            let field_type = field.spy_type;
+            parse_quote! { #field_type }
+        })
+        .collect();
+
+    generics_used_by(
+        &generate_struct_generics(item_trait, associated_spy_types),
+        &field_types,
+    )
+}
+
+fn function_as_spy_arguments(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> TokenStream {
+    let cfg = cfg();
+
+    let visibility = &item_trait.vis;
+    let arguments_name = arguments_name(&format_ident!("{}Spy", item_trait.ident), function);
+    let generics = arguments_generics(item_trait, function, associated_spy_types);
+    let generics_idents = generics_idents(&generics, false);
+    let generics_where_clause = &generics.where_clause;
+
+    let fields = spy_argument_fields(function, associated_spy_types);
+    let is_boxed = fields.iter().any(|field| field.is_boxed);
+    let (names, types): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .map(|field| (field.name, field.spy_type))
+        .unzip();
+
+    if is_boxed {
+        return quote! {
+            #cfg
+            #visibility struct #arguments_name #generics #generics_where_clause {
+                #(pub #names: #types),*
+            }
+        };
+    }
+
+    let indexes = (0..names.len()).map(syn::Index::from);
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let arguments_impls =
+        conditional_derive::struct_impls(&arguments_name, &generics, &names, &types);
+    let partial_eq_where_clause =
+        conditional_derive::where_clause(&generics, &types, &quote! { PartialEq });
+
+    quote! {
+        #cfg
+        #visibility struct #arguments_name #generics #generics_where_clause {
+            #(pub #names: #types),*
+        }
+
+        #arguments_impls
+
+        #cfg
+        impl #impl_generics PartialEq<(#(#types),*)> for #arguments_name #generics_idents #partial_eq_where_clause {
+            fn eq(&self, other: &(#(#types),*)) -> bool {
+                #(self.#names == other.#indexes)&&*
+            }
+        }
+
+        #cfg
+        impl #impl_generics PartialEq<#arguments_name #generics_idents> for (#(#types),*) #partial_eq_where_clause {
+            fn eq(&self, other: &#arguments_name #generics_idents) -> bool {
+                other == self
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_spy_arguments;
+    use crate::associated_types::AssociatedSpyTypes;
+    use crate::options::Options;
+    use quote::quote;
+    use syn::{ItemTrait, parse_quote};
+
+    fn named_arguments() -> Options {
+        Options {
+            named_arguments: true,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn no_arguments_generated_without_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self, x: u8, y: u8);
+            }
+        };
+
+        let actual =
+            generate_spy_arguments(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn functions_with_less_than_two_arguments_are_not_named() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+                fn bar(&self, x: u8);
+                fn baz(&self, #[autospy(ignore)] x: u8, y: u8);
+            }
+        };
+
+        let actual = generate_spy_arguments(&input, &AssociatedSpyTypes::new(), &named_arguments());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn multiple_arguments_are_named() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn send_message(&self, topic: &str, retries: u8);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            struct ExampleSpySendMessageArgs {
+                pub topic: <str as ToOwned>::Owned,
+                pub retries: u8
+            }
+
+            #[cfg(test)]
+            impl Clone for ExampleSpySendMessageArgs
+            where for<'autospy> <str as ToOwned>::Owned: Clone, for<'autospy> u8: Clone
+            {
+                fn clone(&self) -> Self {
+                    Self {
+                        topic: Clone::clone(&self.topic),
+                        retries: Clone::clone(&self.retries)
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl std::fmt::Debug for ExampleSpySendMessageArgs
+            where
+                for<'autospy> <str as ToOwned>::Owned: std::fmt::Debug,
+                for<'autospy> u8: std::fmt::Debug
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_struct("ExampleSpySendMessageArgs")
+                        .field("topic", &self.topic)
+                        .field("retries", &self.retries)
+                        .finish()
+                }
+            }
+
+            #[cfg(test)]
+            impl PartialEq for ExampleSpySendMessageArgs
+            where for<'autospy> <str as ToOwned>::Owned: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    self.topic == other.topic && self.retries == other.retries
+                }
+            }
+
+            #[cfg(test)]
+            impl PartialEq<(<str as ToOwned>::Owned, u8)> for ExampleSpySendMessageArgs
+            where for<'autospy> <str as ToOwned>::Owned: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &(<str as ToOwned>::Owned, u8)) -> bool {
+                    self.topic == other.0 && self.retries == other.1
+                }
+            }
+
+            #[cfg(test)]
+            impl PartialEq<ExampleSpySendMessageArgs> for (<str as ToOwned>::Owned, u8)
+            where for<'autospy> <str as ToOwned>::Owned: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &ExampleSpySendMessageArgs) -> bool {
+                    other == self
+                }
+            }
+        };
+
+        let actual = generate_spy_arguments(&input, &AssociatedSpyTypes::new(), &named_arguments());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn generic_arguments_are_named() {
+        let input: ItemTrait = parse_quote! {
+            pub trait Example<T: Copy, U> {
+                fn foo(&self, x: T, y: u8) -> U;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            pub struct ExampleSpyFooArgs<T: Copy> {
+                pub x: T,
+                pub y: u8
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> Clone for ExampleSpyFooArgs<T>
+            where for<'autospy> T: Clone, for<'autospy> u8: Clone
+            {
+                fn clone(&self) -> Self {
+                    Self {
+                        x: Clone::clone(&self.x),
+                        y: Clone::clone(&self.y)
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> std::fmt::Debug for ExampleSpyFooArgs<T>
+            where for<'autospy> T: std::fmt::Debug, for<'autospy> u8: std::fmt::Debug
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_struct("ExampleSpyFooArgs")
+                        .field("x", &self.x)
+                        .field("y", &self.y)
+                        .finish()
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> PartialEq for ExampleSpyFooArgs<T>
+            where for<'autospy> T: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    self.x == other.x && self.y == other.y
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> PartialEq<(T, u8)> for ExampleSpyFooArgs<T>
+            where for<'autospy> T: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &(T, u8)) -> bool {
+                    self.x == other.0 && self.y == other.1
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> PartialEq<ExampleSpyFooArgs<T> > for (T, u8)
+            where for<'autospy> T: PartialEq, for<'autospy> u8: PartialEq
+            {
+                fn eq(&self, other: &ExampleSpyFooArgs<T>) -> bool {
+                    other == self
+                }
+            }
+        };
+
+        let actual = generate_spy_arguments(&input, &AssociatedSpyTypes::new(), &named_arguments());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn boxed_arguments_do_not_derive() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self, x: impl Fn() + 'static, y: u8);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            struct ExampleSpyFooArgs {
+                pub x: Box<dyn Fn() + 'static>,
+                pub y: u8
+            }
+        };
+
+        let actual = generate_spy_arguments(&input, &AssociatedSpyTypes::new(), &named_arguments());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
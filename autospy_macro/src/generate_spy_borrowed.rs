@@ -0,0 +1,175 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::generate_spy_calls::call_variant;
+use crate::generate_spy_struct::{
+    borrowed_returns_type, extract_lifetimes, generate_struct_generics,
+};
+use crate::generics::generics_used_by;
+use crate::inspect;
+use crate::inspect::cfg;
+use crate::options::Options;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::ItemTrait;
+
+pub fn generate_spy_borrowed(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    let cfg = cfg();
+
+    let visibility = &item_trait.vis;
+    let generics = generate_struct_generics(item_trait, associated_spy_types);
+    let struct_lifetimes = extract_lifetimes(&generics);
+
+    let mut returns_types = HashSet::new();
+    let borrowed_setters = inspect::spy_functions(item_trait)
+        .filter(inspect::is_borrowed_function)
+        .filter_map(|function| {
+            let (returns_type, function_bounds) = borrowed_returns_type(
+                item_trait,
+                &function,
+                associated_spy_types,
+                &struct_lifetimes,
+                options,
+            );
+            // an identical returns type already has a setter, a second would make calls ambiguous
+            if !returns_types.insert(quote! { #returns_type }.to_string()) {
+                return None;
+            }
+
+            let setter_name =
+                format_ident!("{}Spy{}Borrowed", item_trait.ident, call_variant(&function));
+            let setter_generics = generics_used_by(&generics, [&returns_type]);
+            let (impl_generics, type_generics, where_clause) = setter_generics.split_for_impl();
+
+            Some(quote! {
+                #cfg
+                #visibility trait #setter_name #setter_generics #where_clause {
+                    fn set_fn_borrowed(&self, getter: impl #function_bounds + 'static);
+                }
+
+                #cfg
+                impl #impl_generics #setter_name #type_generics for #returns_type #where_clause {
+                    fn set_fn_borrowed(&self, getter: impl #function_bounds + 'static) {
+                        self.set_boxed_fn(Box::new(getter));
+                    }
+                }
+            })
+        });
+
+    quote! {
+        #(#borrowed_setters)*
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_spy_borrowed;
+    use crate::associated_types::AssociatedSpyTypes;
+    use crate::options::Options;
+    use quote::quote;
+    use syn::{ItemTrait, parse_quote};
+
+    #[test]
+    fn no_setter_generated_without_borrowed_functions() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self, text: &str) -> usize;
+            }
+        };
+
+        let actual = generate_spy_borrowed(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn borrowed_functions_have_setter_trait_taking_closure() {
+        let input: ItemTrait = parse_quote! {
+            pub trait Example {
+                #[autospy(borrowed)]
+                fn trim<'a>(&self, text: &'a str) -> &'a str;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            pub trait ExampleSpyTrimBorrowed {
+                fn set_fn_borrowed(
+                    &self,
+                    getter: impl for<'a> FnMut(&'a str) -> &'a str + Send + 'static
+                );
+            }
+
+            #[cfg(test)]
+            impl ExampleSpyTrimBorrowed for autospy::Returns<
+                <str as ToOwned>::Owned,
+                &'static str,
+                dyn for<'a> FnMut(&'a str) -> &'a str + Send
+            > {
+                fn set_fn_borrowed(
+                    &self,
+                    getter: impl for<'a> FnMut(&'a str) -> &'a str + Send + 'static
+                ) {
+                    self.set_boxed_fn(Box::new(getter));
+                }
+            }
+        };
+
+        let actual = generate_spy_borrowed(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn setter_is_generic_over_spy_generics_it_uses() {
+        let input: ItemTrait = parse_quote! {
+            trait Example<T, U> {
+                #[autospy(borrowed)]
+                fn get(&self, value: &T) -> u8;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            trait ExampleSpyGetBorrowed<T> {
+                fn set_fn_borrowed(&self, getter: impl FnMut(&T) -> u8 + Send + 'static);
+            }
+
+            #[cfg(test)]
+            impl<T> ExampleSpyGetBorrowed<T> for autospy::Returns<
+                <T as ToOwned>::Owned,
+                u8,
+                dyn FnMut(&T) -> u8 + Send
+            > {
+                fn set_fn_borrowed(&self, getter: impl FnMut(&T) -> u8 + Send + 'static) {
+                    self.set_boxed_fn(Box::new(getter));
+                }
+            }
+        };
+
+        let actual = generate_spy_borrowed(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn functions_with_identical_returns_type_share_setter() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(borrowed)]
+                fn first(&self, value: &u8);
+                #[autospy(borrowed)]
+                fn second(&self, value: &u8);
+            }
+        };
+
+        let actual = generate_spy_borrowed(&input, &AssociatedSpyTypes::new(), &Options::default())
+            .to_string();
+
+        assert!(actual.contains("ExampleSpyFirstBorrowed"));
+        assert!(!actual.contains("ExampleSpySecondBorrowed"));
+    }
+}
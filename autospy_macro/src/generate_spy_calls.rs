@@ -0,0 +1,324 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::conditional_derive;
+use crate::conditional_derive::Variant;
+use crate::generate_spy_struct::{generate_struct_generics, spy_argument_type};
+use crate::generics::{generics_idents, generics_used_by};
+use crate::inspect;
+use crate::inspect::cfg;
+use crate::options::Options;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Generics, ItemTrait, TraitItemFn, Type, parse_quote};
+
+pub fn generate_spy_calls(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    if !options.calls {
+        return TokenStream::new();
+    }
+
+    let cfg = cfg();
+
+    let visibility = &item_trait.vis;
+    let call_name = call_name(item_trait);
+    let call_generics = call_generics(item_trait, associated_spy_types, options);
+    let call_generics_where_clause = &call_generics.where_clause;
+    let call_variants: Vec<_> = inspect::spy_functions(item_trait)
+        .map(|function| {
+            function_as_call_variant(item_trait, &function, associated_spy_types, options)
+        })
+        .collect();
+    let variant_definitions = call_variants.iter().map(Variant::definition);
+    let call_impls = conditional_derive::enum_impls(&call_name, &call_generics, &call_variants);
+
+    quote! {
+        #cfg
+        #visibility enum #call_name #call_generics #call_generics_where_clause {
+            #(#variant_definitions),*
+        }
+
+        #call_impls
+    }
+}
+
+pub fn call_name(item_trait: &ItemTrait) -> Ident {
+    format_ident!("{}SpyCall", item_trait.ident)
+}
+
+pub fn call_type(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    let call_name = call_name(item_trait);
+    let call_generics_idents = generics_idents(
+        &call_generics(item_trait, associated_spy_types, options),
+        false,
+    );
+    quote! { #call_name #call_generics_idents }
+}
+
+pub fn call_variant(function: &TraitItemFn) -> Ident {
+    let pascal_case: String = function
+        .sig
+        .ident
+        .unraw()
+        .to_string()
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(capitalise)
+        .collect();
+    format_ident!("{}", pascal_case)
+}
+
+fn capitalise(word: &str) -> String {
+    let mut characters = word.chars();
+    characters
+        .next()
+        .map(|first| first.to_uppercase().chain(characters).collect())
+        .unwrap_or_default()
+}
+
+fn call_generics(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> Generics {
+    let argument_types: Vec<Type> = inspect::spy_functions(item_trait)
+        .map(|function| {
+            let spy_argument_type =
+                spy_argument_type(item_trait, &function, associated_spy_types, options);
+            parse_quote! { #spy_argument_type }
+        })
+        .collect();
+
+    generics_used_by(
+        &generate_struct_generics(item_trait, associated_spy_types),
+        &argument_types,
+    )
+}
+
+fn function_as_call_variant(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> Variant {
+    let field_type = inspect::has_spy_arguments(function).then(|| {
+        let spy_argument_type =
+            spy_argument_type(item_trait, function, associated_spy_types, options);
+        quote! { #spy_argument_type }
+    });
+
+    Variant {
+        name: call_variant(function),
+        field_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call_variant, generate_spy_calls};
+    use crate::associated_types::AssociatedSpyTypes;
+    use crate::options::Options;
+    use quote::quote;
+    use syn::{ItemTrait, TraitItemFn, parse_quote};
+
+    fn calls() -> Options {
+        Options {
+            calls: true,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn calls_not_generated_without_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+            }
+        };
+
+        let actual = generate_spy_calls(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn functions_with_and_without_arguments() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+                fn bar_baz(&self, x: &str, y: u8);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            enum ExampleSpyCall {
+                Foo,
+                BarBaz((<str as ToOwned>::Owned, u8))
+            }
+
+            #[cfg(test)]
+            impl Clone for ExampleSpyCall
+            where for<'autospy> (<str as ToOwned>::Owned, u8): Clone
+            {
+                fn clone(&self) -> Self {
+                    match self {
+                        Self::Foo => Self::Foo,
+                        Self::BarBaz(field) => Self::BarBaz(Clone::clone(field)),
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl std::fmt::Debug for ExampleSpyCall
+            where for<'autospy> (<str as ToOwned>::Owned, u8): std::fmt::Debug
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        Self::Foo => f.write_str("Foo"),
+                        Self::BarBaz(field) => f.debug_tuple("BarBaz").field(field).finish(),
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl PartialEq for ExampleSpyCall
+            where for<'autospy> (<str as ToOwned>::Owned, u8): PartialEq
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        (Self::Foo, Self::Foo) => true,
+                        (Self::BarBaz(field), Self::BarBaz(other_field)) => field == other_field,
+                        _ => false,
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_calls(&input, &AssociatedSpyTypes::new(), &calls());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn only_generics_used_by_arguments_are_on_call_enum() {
+        let input: ItemTrait = parse_quote! {
+            pub trait Example<T: Copy, U> {
+                fn foo(&self, x: T) -> U;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            pub enum ExampleSpyCall<T: Copy> {
+                Foo(T)
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> Clone for ExampleSpyCall<T>
+            where for<'autospy> T: Clone
+            {
+                fn clone(&self) -> Self {
+                    match self {
+                        Self::Foo(field) => Self::Foo(Clone::clone(field)),
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> std::fmt::Debug for ExampleSpyCall<T>
+            where for<'autospy> T: std::fmt::Debug
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        Self::Foo(field) => f.debug_tuple("Foo").field(field).finish(),
+                    }
+                }
+            }
+
+            #[cfg(test)]
+            impl<T: Copy> PartialEq for ExampleSpyCall<T>
+            where for<'autospy> T: PartialEq
+            {
+                fn eq(&self, other: &Self) -> bool {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        (Self::Foo(field), Self::Foo(other_field)) => field == other_field,
+                        _ => false,
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_calls(&input, &AssociatedSpyTypes::new(), &calls());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn use_default_functions_are_not_calls() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(use_default)]
+                fn foo(&self) -> u8 {
+                    1
+                }
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            enum ExampleSpyCall {}
+
+            #[cfg(test)]
+            impl Clone for ExampleSpyCall {
+                fn clone(&self) -> Self {
+                    match *self {}
+                }
+            }
+
+            #[cfg(test)]
+            impl std::fmt::Debug for ExampleSpyCall {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match *self {}
+                }
+            }
+
+            #[cfg(test)]
+            impl PartialEq for ExampleSpyCall {
+                fn eq(&self, other: &Self) -> bool {
+                    match *self {}
+                }
+            }
+        };
+
+        let actual = generate_spy_calls(&input, &AssociatedSpyTypes::new(), &calls());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn call_variant_is_pascal_case_of_function_name() {
+        let input: TraitItemFn = parse_quote! {
+            fn read_to_end(&self);
+        };
+
+        assert_eq!("ReadToEnd", call_variant(&input).to_string());
+    }
+
+    #[test]
+    fn call_variant_of_raw_function_name() {
+        let input: TraitItemFn = parse_quote! {
+            fn r#type(&self);
+        };
+
+        assert_eq!("Type", call_variant(&input).to_string());
+    }
+}
@@ -1,69 +1,125 @@
-use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
-use crate::generics::generics_idents;
+use crate::associated_types::AssociatedSpyTypes;
+use crate::generics::spy_generics_idents;
 use crate::inspect::cfg;
+use crate::options::Options;
 use crate::{attribute, inspect, supertraits};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{Generics, ItemTrait, TraitItemFn};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{ItemTrait, TraitItemFn};
 
 pub fn generate_spy_default(
     item_trait: &ItemTrait,
     associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
 ) -> TokenStream {
     let cfg = cfg();
 
     let generics = &item_trait.generics;
-    let generics_idents = generic_idents(item_trait, associated_spy_types);
+    let generics_idents = spy_generics_idents(item_trait, associated_spy_types);
     let generics_where_clause = &generics.where_clause;
 
     let spy_name = format_ident!("{}Spy", &item_trait.ident);
-    let spy_defaults = generate_spy_defaults(item_trait);
+    let spy_defaults = generate_spy_defaults(item_trait)
+        .chain(
+            options
+                .calls
+                .then(|| quote! { autospy_calls: autospy::Calls::default() }),
+        )
+        .chain(options.wrapping.then(|| quote! { autospy_inner: None }));
+    let spy_setup: Vec<_> = generate_spy_diagnostics(item_trait)
+        .chain(
+            inspect::spy_functions(item_trait)
+                .filter_map(|function| function_fallback(&function, options)),
+        )
+        .collect();
+
+    let spy = quote! {
+        Self {
+            #(#spy_defaults),*
+        }
+    };
+
+    let default_body = if spy_setup.is_empty() {
+        spy
+    } else {
+        quote! {
+            let spy = #spy;
+            #(#spy_setup)*
+            spy
+        }
+    };
 
     quote! {
         #cfg
         impl #generics Default for #spy_name #generics_idents #generics_where_clause {
             fn default() -> Self {
-                Self {
-                    #(#spy_defaults),*
-                }
+                #default_body
             }
         }
     }
 }
 
-fn generic_idents(item_trait: &ItemTrait, associated_spy_types: &AssociatedSpyTypes) -> Generics {
-    generics_idents(
-        &item_trait.generics,
-        inspect::has_function_returning_type_containing_elided_lifetime_reference(item_trait)
-            || associated_spy_types
-                .values()
-                .any(AssociatedType::has_lifetime),
-    )
-}
-
 fn generate_spy_defaults(item_trait: &ItemTrait) -> impl Iterator<Item = TokenStream> {
     inspect::trait_functions(item_trait)
         .cloned()
         .chain(
             supertraits::autospy_supertraits(item_trait).flat_map(inspect::owned_trait_functions),
         )
-        .map(|function| function_as_default(&function))
+        .filter_map(|function| function_as_default(&function))
 }
 
-fn function_as_default(function: &TraitItemFn) -> TokenStream {
+fn function_as_default(function: &TraitItemFn) -> Option<TokenStream> {
     if attribute::has_use_default_attribute(&function.attrs) {
-        return TokenStream::new();
+        return None;
     }
 
     let function_ident = &function.sig.ident;
     let function_name = function_ident.to_string();
-    quote! { #function_ident: autospy::SpyFunction::from(#function_name) }
+    Some(quote! { #function_ident: autospy::SpyFunction::from(#function_name) })
+}
+
+fn generate_spy_diagnostics(item_trait: &ItemTrait) -> impl Iterator<Item = TokenStream> {
+    let trait_functions = inspect::trait_functions(item_trait)
+        .cloned()
+        .map(|function| (item_trait.ident.clone(), function));
+    let supertrait_functions =
+        supertraits::autospy_supertraits(item_trait).flat_map(|supertrait| {
+            let supertrait_ident = supertrait.ident.clone();
+            inspect::owned_trait_functions(supertrait)
+                .map(move |function| (supertrait_ident.clone(), function))
+        });
+
+    trait_functions
+        .chain(supertrait_functions)
+        .filter(|(_, function)| !attribute::has_use_default_attribute(&function.attrs))
+        .map(|(trait_ident, function)| {
+            let function_ident = &function.sig.ident;
+            let trait_name = trait_ident.to_string();
+            let function_name = function_ident.to_string();
+            quote! { autospy::__diagnostics!(spy.#function_ident, #trait_name, #function_name); }
+        })
+}
+
+fn function_fallback(function: &TraitItemFn, options: &Options) -> Option<TokenStream> {
+    let function_ident = &function.sig.ident;
+
+    if let Some(default_return) = attribute::default_return(&function.attrs) {
+        return Some(quote! { spy.#function_ident.returns.set_fallback_fn(|_| #default_return); });
+    }
+
+    if !options.nice || inspect::is_recorded_default_function(function) {
+        return None;
+    }
+
+    // spanned so a return type that is not Default is reported at its function
+    Some(quote_spanned! { function_ident.span()=> spy.#function_ident.returns.use_nice_default(); })
 }
 
 #[cfg(test)]
 mod tests {
     use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
     use crate::generate_spy_default::generate_spy_default;
+    use crate::options::Options;
     use quote::quote;
     use syn::{ItemTrait, parse_quote};
 
@@ -84,7 +140,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -101,14 +157,16 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -126,15 +184,18 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo"),
                         bar: autospy::SpyFunction::from("bar")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Example", "bar");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -159,7 +220,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -176,14 +237,16 @@ mod tests {
             #[cfg(test)]
             impl<T> Default for ExampleSpy<T> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -200,14 +263,16 @@ mod tests {
             #[cfg(test)]
             impl<W, O, T> Default for ExampleSpy<W, O, T> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -224,14 +289,16 @@ mod tests {
             #[cfg(test)]
             impl<T: Copy> Default for ExampleSpy<T> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -248,14 +315,16 @@ mod tests {
             #[cfg(test)]
             impl<T> Default for ExampleSpy<T> where T: Copy {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -277,15 +346,18 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo"),
                         bar: autospy::SpyFunction::from("bar")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Supertrait", "bar");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -302,14 +374,16 @@ mod tests {
             #[cfg(test)]
             impl<'a> Default for ExampleSpy<'a> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -326,14 +400,16 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy<'_> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -350,14 +426,16 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy<'_> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -375,15 +453,18 @@ mod tests {
             #[cfg(test)]
             impl<'a> Default for ExampleSpy<'a, '_> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo"),
                         bar: autospy::SpyFunction::from("bar")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Example", "bar");
+                    spy
                 }
             }
         };
 
-        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -417,7 +498,7 @@ mod tests {
             },
         );
 
-        let actual = generate_spy_default(&input, &associated_types);
+        let actual = generate_spy_default(&input, &associated_types, &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -437,9 +518,11 @@ mod tests {
             #[cfg(test)]
             impl Default for ExampleSpy<'_> {
                 fn default() -> Self {
-                    Self {
+                    let spy = Self {
                         foo: autospy::SpyFunction::from("foo")
-                    }
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy
                 }
             }
         };
@@ -454,7 +537,151 @@ mod tests {
             },
         );
 
-        let actual = generate_spy_default(&input, &associated_types);
+        let actual = generate_spy_default(&input, &associated_types, &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn nice_trait_uses_default_fallback_for_all_functions() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self) -> u8;
+                fn bar(&self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Default for ExampleSpy {
+                fn default() -> Self {
+                    let spy = Self {
+                        foo: autospy::SpyFunction::from("foo"),
+                        bar: autospy::SpyFunction::from("bar")
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Example", "bar");
+                    spy.foo.returns.use_nice_default();
+                    spy.bar.returns.use_nice_default();
+                    spy
+                }
+            }
+        };
+
+        let actual = generate_spy_default(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                nice: true,
+                ..Options::default()
+            },
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn default_return_attribute_sets_fallback_for_function() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(default_return = "Ok(())")]
+                fn foo(&self) -> Result<(), String>;
+                fn bar(&self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Default for ExampleSpy {
+                fn default() -> Self {
+                    let spy = Self {
+                        foo: autospy::SpyFunction::from("foo"),
+                        bar: autospy::SpyFunction::from("bar")
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Example", "bar");
+                    spy.foo.returns.set_fallback_fn(|_| Ok(()));
+                    spy
+                }
+            }
+        };
+
+        let actual = generate_spy_default(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn default_return_attribute_takes_precedence_over_nice_trait() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(default_return = "5")]
+                fn foo(&self) -> u8;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Default for ExampleSpy {
+                fn default() -> Self {
+                    let spy = Self {
+                        foo: autospy::SpyFunction::from("foo")
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    spy.foo.returns.set_fallback_fn(|_| 5);
+                    spy
+                }
+            }
+        };
+
+        let actual = generate_spy_default(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                nice: true,
+                ..Options::default()
+            },
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn nice_trait_does_not_use_default_fallback_for_recorded_default_functions() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self) -> u8;
+                #[autospy(record_default)]
+                fn bar(&self) -> Result<u8, String> {
+                    Ok(self.foo())
+                }
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Default for ExampleSpy {
+                fn default() -> Self {
+                    let spy = Self {
+                        foo: autospy::SpyFunction::from("foo"),
+                        bar: autospy::SpyFunction::from("bar")
+                    };
+                    autospy::__diagnostics!(spy.foo, "Example", "foo");
+                    autospy::__diagnostics!(spy.bar, "Example", "bar");
+                    spy.foo.returns.use_nice_default();
+                    spy
+                }
+            }
+        };
+
+        let actual = generate_spy_default(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                nice: true,
+                ..Options::default()
+            },
+        );
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
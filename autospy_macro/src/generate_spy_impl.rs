@@ -0,0 +1,497 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::generate_spy_calls;
+use crate::generate_spy_struct::generate_struct_generics;
+use crate::generics::generics_idents;
+use crate::inspect;
+use crate::inspect::cfg;
+use crate::options::Options;
+use crate::wrapping;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemTrait;
+
+pub fn generate_spy_impl(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    let cfg = cfg();
+
+    let spy_name = format_ident!("{}Spy", item_trait.ident);
+    let generics = generate_struct_generics(item_trait, associated_spy_types);
+    let generics_idents = generics_idents(&generics, false);
+    let generics_where_clause = &generics.where_clause;
+
+    let spy_function_names: Vec<_> = inspect::spy_functions(item_trait)
+        .map(|function| function.sig.ident)
+        .collect();
+
+    let calls = options.calls.then(|| {
+        let call_type = generate_spy_calls::call_type(item_trait, associated_spy_types, options);
+        quote! {
+            #[must_use]
+            pub fn autospy_calls(&self) -> autospy::Calls<#call_type> {
+                self.autospy_calls.clone()
+            }
+        }
+    });
+    let wrapping = options.wrapping.then(|| {
+        let wrapped_trait = wrapping::wrapped_trait(item_trait, associated_spy_types);
+        quote! {
+            pub fn wrapping<AutospyInner>(inner: AutospyInner) -> Self
+            where
+                AutospyInner: #wrapped_trait + Send + Sync + 'static,
+            {
+                let mut spy = Self::default();
+                spy.autospy_inner = Some(std::sync::Arc::new(inner));
+                spy
+            }
+        }
+    });
+    let recording = options.recording.then(|| {
+        let wrapped_trait = wrapping::wrapped_trait(item_trait, associated_spy_types);
+        let recorded_functions: Vec<_> = inspect::spy_functions(item_trait)
+            .filter(|function| wrapping::is_forwarded_function(function, options))
+            .map(|function| function.sig.ident)
+            .collect();
+        let recorded_function_names = recorded_functions.iter().map(ToString::to_string);
+        quote! {
+            autospy::__requires_serde! {
+                "recording",
+                pub fn recording<AutospyInner>(inner: AutospyInner) -> Self
+                where
+                    AutospyInner: #wrapped_trait + Send + Sync + 'static,
+                {
+                    let spy = Self::wrapping(inner);
+                    #(spy.#recorded_functions.record_forwarded_calls();)*
+                    spy
+                }
+
+                #[track_caller]
+                pub fn save_recording(&self, path: impl AsRef<std::path::Path>) {
+                    autospy::__save_recording(
+                        path,
+                        [#((#recorded_function_names, self.#recorded_functions.recorded_interactions())),*],
+                    );
+                }
+            }
+        }
+    });
+    let rendered_interactions = spy_function_names
+        .iter()
+        .map(|function_name| quote! { self.#function_name.interactions() })
+        .chain(
+            options
+                .calls
+                .then(|| quote! { autospy::__calls_interactions!(self.autospy_calls) }),
+        )
+        .collect::<Vec<_>>();
+    let render_interactions = if rendered_interactions.is_empty() {
+        quote! { std::string::String::new() }
+    } else {
+        quote! { [#(#rendered_interactions),*].join("\n") }
+    };
+    let reset_calls = options
+        .calls
+        .then(|| quote! { self.autospy_calls.clear(); });
+
+    quote! {
+        #cfg
+        impl #generics #spy_name #generics_idents #generics_where_clause {
+            #calls
+
+            #wrapping
+
+            #recording
+
+            pub fn autospy_reset(&self) {
+                #(self.#spy_function_names.reset();)*
+                #reset_calls
+            }
+
+            #[track_caller]
+            pub fn autospy_checkpoint(&self) {
+                #(self.#spy_function_names.checkpoint();)*
+            }
+
+            #[track_caller]
+            pub fn autospy_assert_no_more_interactions(&self) {
+                #(self.#spy_function_names.assert_no_more_interactions();)*
+            }
+
+            #[must_use]
+            pub fn autospy_render_interactions(&self) -> std::string::String {
+                #render_interactions
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_spy_impl;
+    use crate::associated_types::AssociatedSpyTypes;
+    use crate::options::Options;
+    use quote::quote;
+    use syn::{ItemTrait, parse_quote};
+
+    #[test]
+    fn no_functions_in_trait() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {}
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                pub fn autospy_reset(&self) {}
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {}
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {}
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    std::string::String::new()
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn multiple_functions_in_trait() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+                fn bar(&self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                    self.bar.reset();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                    self.bar.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                    self.bar.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions(), self.bar.interactions()].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn supertrait_functions_are_included() {
+        let input: ItemTrait = parse_quote! {
+            trait Example: Supertrait {
+                fn foo(&self);
+                autospy::supertrait! {
+                    trait Supertrait {
+                        fn bar(&self);
+                    }
+                }
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                    self.bar.reset();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                    self.bar.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                    self.bar.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions(), self.bar.interactions()].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn use_default_functions_are_not_included() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(use_default)]
+                fn foo(&self) -> u8 {
+                    1
+                }
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                pub fn autospy_reset(&self) {}
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {}
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {}
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    std::string::String::new()
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn generic_trait() {
+        let input: ItemTrait = parse_quote! {
+            trait Example<T: Copy> where T: Send {
+                fn foo(&self, x: T);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl<T: Copy> ExampleSpy<T> where T: Send {
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions()].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn calls_are_included_with_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                #[must_use]
+                pub fn autospy_calls(&self) -> autospy::Calls<ExampleSpyCall> {
+                    self.autospy_calls.clone()
+                }
+
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                    self.autospy_calls.clear();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions(), autospy::__calls_interactions!(self.autospy_calls)].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                calls: true,
+                ..Options::default()
+            },
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn wrapping_constructor_is_included_with_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example<T> {
+                fn foo(&self, x: T);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl<T> ExampleSpy<T> {
+                pub fn wrapping<AutospyInner>(inner: AutospyInner) -> Self
+                where
+                    AutospyInner: Example<T> + Send + Sync + 'static,
+                {
+                    let mut spy = Self::default();
+                    spy.autospy_inner = Some(std::sync::Arc::new(inner));
+                    spy
+                }
+
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions()].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                wrapping: true,
+                ..Options::default()
+            },
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn recording_constructor_records_forwarded_functions_with_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self, x: u8) -> u8;
+                fn bar(&mut self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl ExampleSpy {
+                pub fn wrapping<AutospyInner>(inner: AutospyInner) -> Self
+                where
+                    AutospyInner: Example + Send + Sync + 'static,
+                {
+                    let mut spy = Self::default();
+                    spy.autospy_inner = Some(std::sync::Arc::new(inner));
+                    spy
+                }
+
+                autospy::__requires_serde! {
+                    "recording",
+                    pub fn recording<AutospyInner>(inner: AutospyInner) -> Self
+                    where
+                        AutospyInner: Example + Send + Sync + 'static,
+                    {
+                        let spy = Self::wrapping(inner);
+                        spy.foo.record_forwarded_calls();
+                        spy
+                    }
+
+                    #[track_caller]
+                    pub fn save_recording(&self, path: impl AsRef<std::path::Path>) {
+                        autospy::__save_recording(path, [("foo", self.foo.recorded_interactions())],);
+                    }
+                }
+
+                pub fn autospy_reset(&self) {
+                    self.foo.reset();
+                    self.bar.reset();
+                }
+
+                #[track_caller]
+                pub fn autospy_checkpoint(&self) {
+                    self.foo.checkpoint();
+                    self.bar.checkpoint();
+                }
+
+                #[track_caller]
+                pub fn autospy_assert_no_more_interactions(&self) {
+                    self.foo.assert_no_more_interactions();
+                    self.bar.assert_no_more_interactions();
+                }
+
+                #[must_use]
+                pub fn autospy_render_interactions(&self) -> std::string::String {
+                    [self.foo.interactions(), self.bar.interactions()].join("\n")
+                }
+            }
+        };
+
+        let actual = generate_spy_impl(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options::parse(quote! { recording }),
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
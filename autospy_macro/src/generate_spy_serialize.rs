@@ -0,0 +1,160 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::generate_spy_struct::generate_struct_generics;
+use crate::generics::generics_idents;
+use crate::inspect;
+use crate::inspect::cfg;
+use crate::options::Options;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{ItemTrait, parse_quote};
+
+pub fn generate_spy_serialize(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    if !options.serialize {
+        return TokenStream::new();
+    }
+
+    let cfg = cfg();
+
+    let spy_name = format_ident!("{}Spy", item_trait.ident);
+    let mut generics = generate_struct_generics(item_trait, associated_spy_types);
+    let generics_idents = generics_idents(&generics, false);
+    let type_params: Vec<_> = generics
+        .type_params()
+        .map(|type_param| type_param.ident.clone())
+        .collect();
+    for type_param in type_params {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #type_param: autospy::__serde::Serialize });
+    }
+    let where_clause = &generics.where_clause;
+
+    let spy_function_names: Vec<_> = inspect::spy_functions(item_trait)
+        .map(|function| function.sig.ident)
+        .collect();
+    let serialized_names = spy_function_names
+        .iter()
+        .map(|function_name| function_name.unraw().to_string());
+    let function_count = spy_function_names.len();
+
+    quote! {
+        autospy::__requires_serde! {
+            "serialize",
+            #cfg
+            impl #generics autospy::__serde::Serialize for #spy_name #generics_idents #where_clause {
+                fn serialize<AutospySerializer: autospy::__serde::Serializer>(
+                    &self,
+                    serializer: AutospySerializer,
+                ) -> std::result::Result<AutospySerializer::Ok, AutospySerializer::Error> {
+                    use autospy::__serde::ser::SerializeMap;
+                    let mut map = serializer.serialize_map(std::option::Option::Some(#function_count))?;
+                    #(map.serialize_entry(#serialized_names, &self.#spy_function_names.arguments)?;)*
+                    map.end()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_spy_serialize;
+    use crate::associated_types::AssociatedSpyTypes;
+    use crate::options::Options;
+    use quote::quote;
+    use syn::{ItemTrait, parse_quote};
+
+    fn serialize() -> Options {
+        Options {
+            serialize: true,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn serialize_not_generated_without_option() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+            }
+        };
+
+        let actual =
+            generate_spy_serialize(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn functions_are_serialized_as_map_of_arguments() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self);
+                fn r#type(&self, x: u8);
+            }
+        };
+
+        let expected = quote! {
+            autospy::__requires_serde! {
+                "serialize",
+                #[cfg(test)]
+                impl autospy::__serde::Serialize for ExampleSpy {
+                    fn serialize<AutospySerializer: autospy::__serde::Serializer>(
+                        &self,
+                        serializer: AutospySerializer,
+                    ) -> std::result::Result<AutospySerializer::Ok, AutospySerializer::Error> {
+                        use autospy::__serde::ser::SerializeMap;
+                        let mut map = serializer.serialize_map(std::option::Option::Some(2usize))?;
+                        map.serialize_entry("foo", &self.foo.arguments)?;
+                        map.serialize_entry("type", &self.r#type.arguments)?;
+                        map.end()
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_serialize(&input, &AssociatedSpyTypes::new(), &serialize());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn generic_parameters_must_be_serialize() {
+        let input: ItemTrait = parse_quote! {
+            trait Example<T: Clone> {
+                fn foo(&self, x: T);
+            }
+        };
+
+        let expected = quote! {
+            autospy::__requires_serde! {
+                "serialize",
+                #[cfg(test)]
+                impl<T: Clone> autospy::__serde::Serialize for ExampleSpy<T>
+                where
+                    T: autospy::__serde::Serialize
+                {
+                    fn serialize<AutospySerializer: autospy::__serde::Serializer>(
+                        &self,
+                        serializer: AutospySerializer,
+                    ) -> std::result::Result<AutospySerializer::Ok, AutospySerializer::Error> {
+                        use autospy::__serde::ser::SerializeMap;
+                        let mut map = serializer.serialize_map(std::option::Option::Some(1usize))?;
+                        map.serialize_entry("foo", &self.foo.arguments)?;
+                        map.end()
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_serialize(&input, &AssociatedSpyTypes::new(), &serialize());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
@@ -1,19 +1,25 @@
 use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
 use crate::inspect::cfg;
-use crate::{arguments, attribute, edit, generate, inspect, supertraits};
-use proc_macro2::TokenStream;
+use crate::options::Options;
+use crate::{
+    arguments, attribute, edit, generate, generate_spy_arguments, generate_spy_calls, inspect,
+    owned, supertraits, wrapping,
+};
+use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, format_ident, quote};
 use std::collections::HashSet;
 use syn::fold::Fold;
+use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
 use syn::{
-    GenericParam, Generics, ItemStruct, ItemTrait, Lifetime, LifetimeParam, ReturnType,
+    Fields, GenericParam, Generics, ItemStruct, ItemTrait, Lifetime, LifetimeParam, ReturnType,
     TraitItemFn, Type, TypeImplTrait, TypeReference, parse_quote,
 };
 
 pub fn generate_spy_struct(
     item_trait: &ItemTrait,
     associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
 ) -> ItemStruct {
     let cfg = cfg();
 
@@ -24,18 +30,39 @@ pub fn generate_spy_struct(
 
     let struct_lifetimes = extract_lifetimes(&generics);
 
-    let spy_fields = generate_spy_fields(item_trait, associated_spy_types, struct_lifetimes);
+    let spy_fields =
+        generate_spy_fields(item_trait, associated_spy_types, struct_lifetimes, options);
 
-    parse_quote! {
+    let mut spy_struct: ItemStruct = parse_quote! {
         #cfg
         #[derive(Clone)]
         #visibility struct #spy_name #generics #generics_where_clause {
             #(#spy_fields),*
         }
+    };
+
+    if options.calls
+        && let Fields::Named(fields) = &mut spy_struct.fields
+    {
+        let call_type = generate_spy_calls::call_type(item_trait, associated_spy_types, options);
+        fields.named.push(parse_quote! {
+            autospy_calls: autospy::Calls<#call_type>
+        });
     }
+
+    if options.wrapping
+        && let Fields::Named(fields) = &mut spy_struct.fields
+    {
+        let inner_type = wrapping::inner_type(item_trait, associated_spy_types);
+        fields.named.push(parse_quote! {
+            autospy_inner: Option<#inner_type>
+        });
+    }
+
+    spy_struct
 }
 
-fn generate_struct_generics(
+pub fn generate_struct_generics(
     item_trait: &ItemTrait,
     associated_spy_types: &AssociatedSpyTypes,
 ) -> Generics {
@@ -55,7 +82,7 @@ fn generate_struct_generics(
     generics
 }
 
-fn extract_lifetimes(generics: &Generics) -> HashSet<&Lifetime> {
+pub fn extract_lifetimes(generics: &Generics) -> HashSet<&Lifetime> {
     generics
         .params
         .iter()
@@ -66,44 +93,113 @@ fn extract_lifetimes(generics: &Generics) -> HashSet<&Lifetime> {
         .collect()
 }
 
-fn generate_spy_fields(
-    item_trait: &ItemTrait,
-    associated_spy_types: &AssociatedSpyTypes,
-    struct_lifetimes: HashSet<&syn::Lifetime>,
-) -> impl Iterator<Item = TokenStream> {
+fn generate_spy_fields<'a>(
+    item_trait: &'a ItemTrait,
+    associated_spy_types: &'a AssociatedSpyTypes,
+    struct_lifetimes: HashSet<&'a syn::Lifetime>,
+    options: &'a Options,
+) -> impl Iterator<Item = TokenStream> + 'a {
     inspect::trait_functions(item_trait)
         .cloned()
         .chain(
             supertraits::autospy_supertraits(item_trait).flat_map(inspect::owned_trait_functions),
         )
         .map(move |function| {
-            function_as_spy_field(&function, associated_spy_types, &struct_lifetimes)
+            function_as_spy_field(
+                item_trait,
+                &function,
+                associated_spy_types,
+                &struct_lifetimes,
+                options,
+            )
         })
 }
 
 fn function_as_spy_field(
+    item_trait: &ItemTrait,
     function: &TraitItemFn,
     associated_spy_types: &AssociatedSpyTypes,
     struct_lifetimes: &HashSet<&syn::Lifetime>,
+    options: &Options,
 ) -> TokenStream {
-    if attribute::has_use_default_attribute(&function.attrs) && function.default.is_some() {
+    if !inspect::has_spy_function(function) {
         return TokenStream::new();
     }
 
     let function_name = &function.sig.ident;
 
-    let function = replace_associated_types(function.clone(), associated_spy_types);
+    let spy_argument_type = spy_argument_type(item_trait, function, associated_spy_types, options);
 
-    let spy_argument_type =
-        generate::tuple_or_single(arguments::spy_arguments(&function).map(argument_spy_type));
+    let function = replace_associated_types(function.clone(), associated_spy_types);
 
     let return_type = function_return_type(&function, struct_lifetimes);
 
+    if inspect::is_borrowed_function(&function) {
+        let borrowed_function_bounds = borrowed_function_bounds(&function, struct_lifetimes);
+        return quote! {
+            pub #function_name: autospy::SpyFunction<
+                #spy_argument_type,
+                #return_type,
+                dyn #borrowed_function_bounds
+            >
+        };
+    }
+
+    if inspect::has_out_arguments(&function) {
+        let out_types =
+            arguments::out_arguments(&function).map(|argument| argument.referenced_type);
+        return quote! {
+            pub #function_name: autospy::SpyFunction<
+                #spy_argument_type,
+                #return_type,
+                dyn FnMut(&#spy_argument_type, #(&mut #out_types),*) -> #return_type + Send
+            >
+        };
+    }
+
     quote! {
         pub #function_name: autospy::SpyFunction<#spy_argument_type, #return_type>
     }
 }
 
+pub fn spy_argument_type(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
+) -> TokenStream {
+    if generate_spy_arguments::has_named_arguments(function, options) {
+        return generate_spy_arguments::arguments_type(item_trait, function, associated_spy_types);
+    }
+
+    generate::tuple_or_single(
+        spy_argument_fields(function, associated_spy_types)
+            .into_iter()
+            .map(|field| field.spy_type),
+    )
+}
+
+pub struct SpyArgumentField {
+    pub name: Ident,
+    pub spy_type: TokenStream,
+    pub is_boxed: bool,
+}
+
+pub fn spy_argument_fields(
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> Vec<SpyArgumentField> {
+    let function = replace_associated_types(function.clone(), associated_spy_types);
+    arguments::spy_arguments(&function)
+        .map(|argument| SpyArgumentField {
+            name: argument.name.clone(),
+            is_boxed: argument.into_type.is_none()
+                && matches!(argument.dereferenced_type, Type::ImplTrait(_)),
+            spy_type: argument_spy_type(argument),
+        })
+        .collect()
+}
+
 fn replace_associated_types(
     mut function: TraitItemFn,
     associated_spy_types: &AssociatedSpyTypes,
@@ -121,6 +217,10 @@ fn argument_spy_type(argument: arguments::SpyArgument) -> TokenStream {
     }
 
     let dereferenced_type = &argument.dereferenced_type;
+    if owned::contains_reference(dereferenced_type) {
+        return owned::owned_type(dereferenced_type).to_token_stream();
+    }
+
     match argument.dereferenced_type {
         Type::ImplTrait(TypeImplTrait { bounds, .. }) => quote! { Box<dyn #bounds> },
         _ if argument.dereference_count == 0 => quote! { #dereferenced_type },
@@ -128,16 +228,92 @@ fn argument_spy_type(argument: arguments::SpyArgument) -> TokenStream {
     }
 }
 
+pub fn borrowed_returns_type(
+    item_trait: &ItemTrait,
+    function: &TraitItemFn,
+    associated_spy_types: &AssociatedSpyTypes,
+    struct_lifetimes: &HashSet<&Lifetime>,
+    options: &Options,
+) -> (Type, TokenStream) {
+    let spy_argument_type = spy_argument_type(item_trait, function, associated_spy_types, options);
+    let function = replace_associated_types(function.clone(), associated_spy_types);
+    let return_type = function_return_type(&function, struct_lifetimes);
+    let borrowed_function_bounds = borrowed_function_bounds(&function, struct_lifetimes);
+    (
+        parse_quote! {
+            autospy::Returns<#spy_argument_type, #return_type, dyn #borrowed_function_bounds>
+        },
+        borrowed_function_bounds,
+    )
+}
+
+fn borrowed_function_bounds(
+    function: &TraitItemFn,
+    struct_lifetimes: &HashSet<&Lifetime>,
+) -> TokenStream {
+    let borrowed_types: Vec<Type> = arguments::borrowed_arguments(function)
+        .map(|argument| argument.borrowed_type)
+        .collect();
+
+    let mut argument_lifetimes = LifetimeCollector::default();
+    for borrowed_type in &borrowed_types {
+        argument_lifetimes.visit_type(borrowed_type);
+    }
+
+    let (bound_lifetimes, static_lifetimes): (HashSet<_>, HashSet<_>) =
+        extract_lifetimes(&function.sig.generics)
+            .into_iter()
+            .partition(|lifetime| argument_lifetimes.lifetimes.contains(lifetime));
+
+    let for_lifetimes = (!bound_lifetimes.is_empty()).then(|| {
+        let bound_lifetimes = function
+            .sig
+            .generics
+            .lifetimes()
+            .map(|param| &param.lifetime)
+            .filter(|lifetime| bound_lifetimes.contains(lifetime));
+        quote! { for<#(#bound_lifetimes),*> }
+    });
+
+    let return_type =
+        function_return_type_with_lifetimes(function, static_lifetimes, struct_lifetimes);
+
+    quote! {
+        #for_lifetimes FnMut(#(#borrowed_types),*) -> #return_type + Send
+    }
+}
+
+#[derive(Default)]
+struct LifetimeCollector {
+    lifetimes: HashSet<Lifetime>,
+}
+
+impl Visit<'_> for LifetimeCollector {
+    fn visit_lifetime(&mut self, lifetime: &Lifetime) {
+        self.lifetimes.insert(lifetime.clone());
+    }
+}
+
 fn function_return_type(
     function: &TraitItemFn,
     struct_lifetimes: &HashSet<&Lifetime>,
+) -> TokenStream {
+    function_return_type_with_lifetimes(
+        function,
+        extract_lifetimes(&function.sig.generics),
+        struct_lifetimes,
+    )
+}
+
+fn function_return_type_with_lifetimes(
+    function: &TraitItemFn,
+    function_lifetimes: HashSet<&Lifetime>,
+    struct_lifetimes: &HashSet<&Lifetime>,
 ) -> TokenStream {
     if let Some(specified_return_type) = attribute::return_type(&function.attrs) {
         return specified_return_type.to_token_stream();
     }
 
-    let function_lifetimes = extract_lifetimes(&function.sig.generics);
-
     match &function.sig.output {
         ReturnType::Default => quote! { () },
         ReturnType::Type(_, return_type) => {
@@ -180,6 +356,7 @@ impl Fold for LifetimeFolder<'_> {
 mod tests {
     use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
     use crate::generate_spy_struct::generate_spy_struct;
+    use crate::options::Options;
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::{ItemStruct, ItemTrait, parse_quote};
@@ -198,7 +375,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -220,7 +397,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -242,7 +419,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -264,7 +441,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -286,7 +463,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -309,7 +486,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -331,7 +508,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -353,7 +530,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -375,7 +552,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -400,7 +577,8 @@ mod tests {
             expected,
             generate_spy_struct(
                 &input,
-                &associated_spy_types(quote! { Item }, quote! { &'a u32 })
+                &associated_spy_types(quote! { Item }, quote! { &'a u32 }),
+                &Options::default()
             )
         );
     }
@@ -425,7 +603,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -451,7 +629,8 @@ mod tests {
             expected,
             generate_spy_struct(
                 &input,
-                &associated_spy_types(quote! { Item }, quote! { String })
+                &associated_spy_types(quote! { Item }, quote! { String }),
+                &Options::default()
             )
         );
     }
@@ -478,7 +657,8 @@ mod tests {
             expected,
             generate_spy_struct(
                 &input,
-                &associated_spy_types(quote! { Item }, quote! { String })
+                &associated_spy_types(quote! { Item }, quote! { String }),
+                &Options::default()
             )
         );
     }
@@ -505,7 +685,8 @@ mod tests {
             expected,
             generate_spy_struct(
                 &input,
-                &associated_spy_types(quote! { Item }, quote! { &'a str })
+                &associated_spy_types(quote! { Item }, quote! { &'a str }),
+                &Options::default()
             )
         );
     }
@@ -534,7 +715,8 @@ mod tests {
             expected,
             generate_spy_struct(
                 &input,
-                &associated_spy_types(quote! { Item }, quote! { &'b str })
+                &associated_spy_types(quote! { Item }, quote! { &'b str }),
+                &Options::default()
             )
         );
     }
@@ -558,7 +740,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -580,7 +762,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -602,7 +784,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -624,7 +806,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -652,7 +834,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -674,7 +856,60 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
+        );
+    }
+
+    #[test]
+    fn generated_spy_struct_has_mutable_return_function_for_out_arguments() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn foo(&self, count: usize, #[autospy(out)] buffer: &mut [u8]) -> usize;
+            }
+        };
+
+        let expected: ItemStruct = parse_quote! {
+            #[cfg(test)]
+            #[derive(Clone)]
+             struct ExampleSpy {
+                pub foo: autospy::SpyFunction<
+                    (usize, <[u8] as ToOwned>::Owned),
+                    usize,
+                    dyn FnMut(&(usize, <[u8] as ToOwned>::Owned), &mut [u8]) -> usize + Send
+                >
+            }
+        };
+
+        assert_eq!(
+            expected,
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
+        );
+    }
+
+    #[test]
+    fn generated_spy_struct_has_borrowed_return_function_for_borrowed_functions() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(borrowed)]
+                fn foo<'a>(&self, text: &'a str, count: u8) -> &'a str;
+            }
+        };
+
+        let expected: ItemStruct = parse_quote! {
+            #[cfg(test)]
+            #[derive(Clone)]
+             struct ExampleSpy {
+                pub foo: autospy::SpyFunction<
+                    (<str as ToOwned>::Owned, u8),
+                    &'static str,
+                    dyn for<'a> FnMut(&'a str, &u8) -> &'a str + Send
+                >
+            }
+        };
+
+        assert_eq!(
+            expected,
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -696,7 +931,7 @@ mod tests {
 
         assert_eq!(
             expected,
-            generate_spy_struct(&input, &AssociatedSpyTypes::new())
+            generate_spy_struct(&input, &AssociatedSpyTypes::new(), &Options::default())
         );
     }
 
@@ -1,15 +1,26 @@
 use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
-use crate::generics::generics_idents;
+use crate::generics::{generics_idents, spy_generics_idents};
 use crate::inspect::cfg;
+use crate::options::Options;
 use crate::strip_attributes::{strip_attributes_from_signature, strip_autospy_attributes};
-use crate::{arguments, attribute, edit, generate, inspect, supertraits};
+use crate::{
+    arguments, attribute, edit, generate, generate_spy_arguments, generate_spy_calls, inspect,
+    owned, supertraits, wrapping,
+};
 use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
-use syn::{Generics, ItemTrait, Token, TraitItemConst, TraitItemFn, Type, parse_quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Block, Generics, ItemTrait, Signature, Token, TraitItemConst, TraitItemFn, Type, parse_quote,
+};
+
+// matches the arities `set_fn_mut` is implemented for in the runtime crate
+const MAX_OUT_ARGUMENTS: usize = 4;
 
 pub fn generate_spy_trait(
     item_trait: &ItemTrait,
     associated_spy_types: &AssociatedSpyTypes,
+    options: &Options,
 ) -> TokenStream {
     let cfg = cfg();
 
@@ -26,9 +37,13 @@ pub fn generate_spy_trait(
     let spy_name = format_ident!("{}Spy", trait_name);
     let associated_type_definitions = associated_type_definitions(associated_spy_types);
     let spy_associated_consts = spy_associated_consts(item_trait);
-    let spy_function_definitions = trait_spy_function_definitions(item_trait);
+    let call_name = options
+        .calls
+        .then(|| generate_spy_calls::call_name(item_trait));
+    let spy_function_definitions =
+        trait_spy_function_definitions(item_trait, &spy_name, call_name.as_ref(), options);
 
-    let spy_supertraits = spy_supertraits(&cfg, &spy_name, item_trait);
+    let spy_supertraits = spy_supertraits(&cfg, &spy_name, item_trait, call_name.as_ref(), options);
 
     quote! {
         #cfg
@@ -47,13 +62,7 @@ fn generic_idents_with_and_without_elided_lifetime(
     associated_spy_types: &AssociatedSpyTypes,
 ) -> (Generics, Generics) {
     (
-        generics_idents(
-            &item_trait.generics,
-            inspect::has_function_returning_type_containing_elided_lifetime_reference(item_trait)
-                || associated_spy_types
-                    .values()
-                    .any(AssociatedType::has_lifetime),
-        ),
+        spy_generics_idents(item_trait, associated_spy_types),
         generics_idents(&item_trait.generics, false),
     )
 }
@@ -69,11 +78,22 @@ fn associated_type_definitions(
         })
 }
 
-fn trait_spy_function_definitions(item_trait: &ItemTrait) -> impl Iterator<Item = TokenStream> {
-    inspect::trait_functions(item_trait).map(function_as_spy_function)
+fn trait_spy_function_definitions<'a>(
+    item_trait: &'a ItemTrait,
+    spy_name: &'a Ident,
+    call_name: Option<&'a Ident>,
+    options: &'a Options,
+) -> impl Iterator<Item = TokenStream> + 'a {
+    inspect::trait_functions(item_trait)
+        .map(move |function| function_as_spy_function(function, spy_name, call_name, options))
 }
 
-fn function_as_spy_function(function: &TraitItemFn) -> TokenStream {
+fn function_as_spy_function(
+    function: &TraitItemFn,
+    spy_name: &Ident,
+    call_name: Option<&Ident>,
+    options: &Options,
+) -> TokenStream {
     let mut signature = function.sig.clone();
 
     if attribute::has_use_default_attribute(&function.attrs)
@@ -83,38 +103,342 @@ fn function_as_spy_function(function: &TraitItemFn) -> TokenStream {
     }
 
     let function_name = &function.sig.ident;
-    let spy_arguments = generate::tuple_or_single(
-        arguments::spy_arguments(function).map(argument_to_spy_expression),
-    );
+    // forwarded and recorded default functions still use the arguments after they are spied
+    let is_cloned = wrapping::is_forwarded_function(function, options)
+        || inspect::is_recorded_default_function(function);
+    let spy_arguments = function_spy_arguments(function, spy_name, is_cloned, options);
+    let moved_spy_arguments = function_spy_arguments(function, spy_name, false, options);
+    let arguments_variable = quote! { arguments };
+
+    let record_call = call_name.map(|call_name| {
+        let call = spy_call(function, call_name);
+        quote! { self.autospy_calls.record(#call); }
+    });
+
+    if attribute::has_record_default_attribute(&function.attrs)
+        && let Some(default_function) = &function.default
+    {
+        strip_attributes_from_signature(&mut signature);
+        return recorded_default_function(
+            &signature,
+            default_function,
+            &spy_arguments,
+            record_call.as_ref(),
+        );
+    }
 
-    edit::underscore_ignored_arguments_in_signature(&mut signature);
+    if inspect::is_borrowed_function(function) {
+        strip_attributes_from_signature(&mut signature);
+        let borrowed_expressions = arguments::borrowed_arguments(function)
+            .map(|argument| borrowed_argument_expression(&argument));
+        let call_fn = quote! {
+            let return_value = self.#function_name
+                .returns
+                .call_fn_mut(|getter| getter(#(#borrowed_expressions),*));
+        };
+        let Some(record_call) = record_call else {
+            let capture_or_spy =
+                capture_or_spy(function, &moved_spy_arguments, &spy_arguments, options);
+            return quote! {
+                #[track_caller]
+                #signature {
+                    #call_fn
+                    #capture_or_spy
+                }
+            };
+        };
+        let capture_or_spy =
+            capture_or_spy(function, &arguments_variable, &arguments_variable, options);
+        return quote! {
+            #[track_caller]
+            #signature {
+                #call_fn
+                let arguments = #spy_arguments;
+                #record_call
+                #capture_or_spy
+            }
+        };
+    }
+
+    if !wrapping::is_forwarded_function(function, options) {
+        edit::underscore_ignored_arguments_in_signature(&mut signature);
+    }
     strip_attributes_from_signature(&mut signature);
 
+    if inspect::has_out_arguments(function) {
+        return out_arguments_function(
+            function,
+            &signature,
+            &spy_arguments,
+            record_call.as_ref(),
+            options,
+        );
+    }
+
+    if let Some(record_call) = record_call {
+        let spy = spy(function, &arguments_variable, &arguments_variable, options);
+        return quote! {
+            #[track_caller]
+            #signature {
+                let arguments = #spy_arguments;
+                #record_call
+                #spy
+            }
+        };
+    }
+
+    let spy = spy(function, &moved_spy_arguments, &spy_arguments, options);
+    quote! {
+        #[track_caller]
+        #signature {
+            #spy
+        }
+    }
+}
+
+fn out_arguments_function(
+    function: &TraitItemFn,
+    signature: &Signature,
+    spy_arguments: &TokenStream,
+    record_call: Option<&TokenStream>,
+    options: &Options,
+) -> TokenStream {
+    let function_name = &function.sig.ident;
+    let out_arguments: Vec<_> = arguments::out_arguments(function).collect();
+    let arity_error = out_arity_error(function, out_arguments.len());
+    let out_names = out_arguments.iter().map(|argument| &argument.name);
+    let written_names: Vec<_> = out_arguments
+        .iter()
+        .filter(|argument| argument.capture_written)
+        .map(|argument| argument.name.clone())
+        .collect();
+    let out_argument_updates = out_argument_updates(function, &written_names, options);
+    let mutability = (!out_argument_updates.is_empty()).then(|| quote! { mut });
+    let arguments_variable = quote! { arguments };
+    let capture_or_spy =
+        capture_or_spy(function, &arguments_variable, &arguments_variable, options);
+    quote! {
+        #[track_caller]
+        #signature {
+            #arity_error
+            let #mutability arguments = #spy_arguments;
+            let return_value = self.#function_name
+                .returns
+                .call_fn_mut(|getter| getter(&arguments, #(&mut *#out_names),*));
+            #(#out_argument_updates)*
+            #record_call
+            #capture_or_spy
+        }
+    }
+}
+
+fn out_arity_error(function: &TraitItemFn, out_count: usize) -> Option<TokenStream> {
+    (out_count > MAX_OUT_ARGUMENTS).then(|| {
+        syn::Error::new_spanned(
+            &function.sig.ident,
+            format!("autospy supports at most {MAX_OUT_ARGUMENTS} out arguments per function"),
+        )
+        .to_compile_error()
+    })
+}
+
+fn recorded_default_function(
+    signature: &Signature,
+    default_function: &Block,
+    spy_arguments: &TokenStream,
+    record_call: Option<&TokenStream>,
+) -> TokenStream {
+    let function_name = &signature.ident;
+    let statements = &default_function.stmts;
+    let capture = record_call.map_or_else(
+        || quote! { self.#function_name.capture(#spy_arguments); },
+        |record_call| {
+            quote! {
+                let arguments = #spy_arguments;
+                #record_call
+                self.#function_name.capture(arguments);
+            }
+        },
+    );
+
     quote! {
         #[track_caller]
         #signature {
-            self.#function_name.spy(#spy_arguments)
+            #capture
+            #(#statements)*
+        }
+    }
+}
+
+fn spy(
+    function: &TraitItemFn,
+    spy_arguments: &TokenStream,
+    forwarded_spy_arguments: &TokenStream,
+    options: &Options,
+) -> TokenStream {
+    let function_name = &function.sig.ident;
+
+    let spy = if function.sig.asyncness.is_some() {
+        quote! { self.#function_name.spy_async(#spy_arguments).await }
+    } else {
+        quote! { self.#function_name.spy(#spy_arguments) }
+    };
+
+    if !wrapping::is_forwarded_function(function, options) {
+        return spy;
+    }
+
+    let forward_call = wrapping::forward_call(function);
+    let spy_or_forward = if function.sig.asyncness.is_some() {
+        quote! {
+            self.#function_name.spy_async_or_else(#forwarded_spy_arguments, || #forward_call).await
         }
+    } else {
+        quote! { self.#function_name.spy_or_else(#forwarded_spy_arguments, || #forward_call) }
+    };
+
+    quote! {
+        match &self.autospy_inner {
+            Some(autospy_inner) => #spy_or_forward,
+            None => #spy,
+        }
+    }
+}
+
+fn function_spy_arguments(
+    function: &TraitItemFn,
+    spy_name: &Ident,
+    is_cloned: bool,
+    options: &Options,
+) -> TokenStream {
+    if generate_spy_arguments::has_named_arguments(function, options) {
+        let struct_name = generate_spy_arguments::arguments_name(spy_name, function);
+        let fields = arguments::spy_arguments(function).map(|argument| {
+            let argument_name = argument.name.clone();
+            let spy_expression = argument_to_spy_expression(argument, is_cloned);
+            quote! { #argument_name: #spy_expression }
+        });
+        return quote! { #struct_name { #(#fields),* } };
     }
+
+    generate::tuple_or_single(
+        arguments::spy_arguments(function)
+            .map(|argument| argument_to_spy_expression(argument, is_cloned)),
+    )
 }
 
-fn argument_to_spy_expression(argument: arguments::SpyArgument) -> TokenStream {
+fn capture_or_spy(
+    function: &TraitItemFn,
+    spy_arguments: &TokenStream,
+    forwarded_spy_arguments: &TokenStream,
+    options: &Options,
+) -> TokenStream {
+    let function_name = &function.sig.ident;
+    let spy = spy(function, spy_arguments, forwarded_spy_arguments, options);
+
+    let capture = if function.sig.asyncness.is_some() {
+        quote! { self.#function_name.capture_async(#spy_arguments, return_value).await }
+    } else {
+        quote! {
+            self.#function_name.capture(#spy_arguments);
+            return_value
+        }
+    };
+
+    quote! {
+        match return_value {
+            Some(return_value) => {
+                #capture
+            }
+            None => #spy,
+        }
+    }
+}
+
+fn borrowed_argument_expression(argument: &arguments::BorrowedArgument) -> TokenStream {
+    let argument_name = &argument.name;
+
+    match argument.borrow {
+        arguments::Borrow::Owned => quote! { &#argument_name },
+        arguments::Borrow::Shared => quote! { #argument_name },
+        arguments::Borrow::Mutable => quote! { &mut *#argument_name },
+    }
+}
+
+fn out_argument_updates(
+    function: &TraitItemFn,
+    written_names: &[Ident],
+    options: &Options,
+) -> Vec<TokenStream> {
+    let is_named = generate_spy_arguments::has_named_arguments(function, options);
+    let is_single = arguments::spy_arguments(function).nth(1).is_none();
+
+    arguments::spy_arguments(function)
+        .enumerate()
+        .filter(|(_, argument)| written_names.contains(&argument.name))
+        .map(|(index, argument)| {
+            let argument_name = argument.name.clone();
+            let spy_expression = argument_to_spy_expression(
+                argument,
+                wrapping::is_forwarded_function(function, options),
+            );
+            if is_named {
+                quote! { arguments.#argument_name = #spy_expression; }
+            } else if is_single {
+                quote! { arguments = #spy_expression; }
+            } else {
+                let index = syn::Index::from(index);
+                quote! { arguments.#index = #spy_expression; }
+            }
+        })
+        .collect()
+}
+
+fn spy_call(function: &TraitItemFn, call_name: &Ident) -> TokenStream {
+    let call_variant = generate_spy_calls::call_variant(function);
+
+    if inspect::has_spy_arguments(function) {
+        // spanned so a call argument type that is not Clone is reported at its function
+        let clone = quote_spanned! { function.sig.span()=> Clone::clone };
+        quote! { #call_name::#call_variant(#clone(&arguments)) }
+    } else {
+        quote! { #call_name::#call_variant }
+    }
+}
+
+fn argument_to_spy_expression(argument: arguments::SpyArgument, is_cloned: bool) -> TokenStream {
     let argument_name = &argument.name;
+    let owned_argument = if is_cloned && argument.dereference_count == 0 {
+        quote! { Clone::clone(&#argument_name) }
+    } else {
+        quote! { #argument_name }
+    };
 
     if let Some(with_expression) = argument.with_expression {
-        return quote! { #with_expression ( #argument_name ) };
+        return quote! { #with_expression ( #owned_argument ) };
     }
 
     if argument.into_type.is_some() {
-        return quote! { #argument_name.into() };
+        return quote! { #owned_argument.into() };
     }
 
     if let Type::ImplTrait(_) = argument.dereferenced_type {
-        return quote! { Box::new(#argument_name) };
+        return quote! { Box::new(#owned_argument) };
+    }
+
+    if owned::contains_reference(&argument.dereferenced_type) {
+        let reference = if argument.dereference_count == 0 {
+            quote! { (&#argument_name) }
+        } else {
+            let dereferences = dereference_tokens(&argument);
+            quote! { (#dereferences #argument_name) }
+        };
+        return owned::to_owned_expression(&argument.dereferenced_type, &reference)
+            .unwrap_or_else(syn::Error::into_compile_error);
     }
 
     match argument.dereference_count {
-        0 => quote! { #argument_name },
+        0 => owned_argument,
         1 => quote! { #argument_name.to_owned() },
         _ => {
             let dereferences = dereference_tokens(&argument);
@@ -156,18 +480,24 @@ fn spy_supertraits(
     cfg: &TokenStream,
     spy_name: &Ident,
     item_trait: &ItemTrait,
+    call_name: Option<&Ident>,
+    options: &Options,
 ) -> impl Iterator<Item = TokenStream> {
-    supertraits::autospy_supertraits(item_trait)
-        .map(|supertrait| supertrait_as_spy_trait(cfg, &supertrait, spy_name))
+    supertraits::autospy_supertraits(item_trait).map(move |supertrait| {
+        supertrait_as_spy_trait(cfg, &supertrait, spy_name, call_name, options)
+    })
 }
 
 fn supertrait_as_spy_trait(
     cfg: &TokenStream,
     supertrait: &ItemTrait,
     spy_name: &Ident,
+    call_name: Option<&Ident>,
+    options: &Options,
 ) -> TokenStream {
     let supertrait_name = &supertrait.ident;
-    let spy_function_definitions = trait_spy_function_definitions(supertrait);
+    let spy_function_definitions =
+        trait_spy_function_definitions(supertrait, spy_name, call_name, options);
 
     quote! {
         #cfg
@@ -180,6 +510,7 @@ fn supertrait_as_spy_trait(
 #[cfg(test)]
 mod tests {
     use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
+    use crate::options::Options;
 
     use super::generate_spy_trait;
     use quote::quote;
@@ -196,7 +527,7 @@ mod tests {
             impl Example for ExampleSpy {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -212,7 +543,7 @@ mod tests {
             impl Example for ExampleSpy {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -230,7 +561,7 @@ mod tests {
             impl Example for ExampleSpy {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -250,12 +581,12 @@ mod tests {
             impl Example for ExampleSpy {
                 #[track_caller]
                 async fn function(&self) {
-                    self.function.spy(())
+                    self.function.spy_async(()).await
                 }
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -278,7 +609,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -301,7 +632,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -324,7 +655,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -347,7 +678,196 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn async_out_argument_functions_are_tracked() {
+        let input: ItemTrait = parse_quote! {
+            #[async_trait]
+            trait Example {
+                async fn function(&self, #[autospy(out)] buffer: &mut [u8]) -> usize;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            #[async_trait]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                async fn function(&self, buffer: &mut [u8]) -> usize {
+                    let arguments = buffer.to_owned();
+                    let return_value = self.function
+                        .returns
+                        .call_fn_mut(|getter| getter(&arguments, &mut *buffer));
+                    match return_value {
+                        Some(return_value) => {
+                            self.function.capture_async(arguments, return_value).await
+                        }
+                        None => self.function.spy_async(arguments).await,
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn out_arguments_are_passed_to_mutable_return_function_and_captured_before() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn function(&self, count: usize, #[autospy(out)] buffer: &mut [u8]) -> usize;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn function(&self, count: usize, buffer: &mut [u8]) -> usize {
+                    let arguments = (count, buffer.to_owned());
+                    let return_value = self.function
+                        .returns
+                        .call_fn_mut(|getter| getter(&arguments, &mut *buffer));
+                    match return_value {
+                        Some(return_value) => {
+                            self.function.capture(arguments);
+                            return_value
+                        }
+                        None => self.function.spy(arguments),
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn out_arguments_marked_with_capture_written_are_captured_after() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn function(&self, count: usize, #[autospy(out)] #[autospy(capture_written)] buffer: &mut [u8]) -> usize;
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn function(&self, count: usize, buffer: &mut [u8]) -> usize {
+                    let mut arguments = (count, buffer.to_owned());
+                    let return_value = self.function
+                        .returns
+                        .call_fn_mut(|getter| getter(&arguments, &mut *buffer));
+                    arguments.1 = buffer.to_owned();
+                    match return_value {
+                        Some(return_value) => {
+                            self.function.capture(arguments);
+                            return_value
+                        }
+                        None => self.function.spy(arguments),
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn ignored_out_arguments_are_passed_to_mutable_return_function() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn function(&self, #[autospy(ignore)] #[autospy(out)] buffer: &mut [u8]);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn function(&self, buffer: &mut [u8]) {
+                    let arguments = ();
+                    let return_value = self.function
+                        .returns
+                        .call_fn_mut(|getter| getter(&arguments, &mut *buffer));
+                    match return_value {
+                        Some(return_value) => {
+                            self.function.capture(arguments);
+                            return_value
+                        }
+                        None => self.function.spy(arguments),
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn more_out_arguments_than_set_fn_mut_supports_is_compile_error() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn function(
+                    &self,
+                    #[autospy(out)] a: &mut u8,
+                    #[autospy(out)] b: &mut u8,
+                    #[autospy(out)] c: &mut u8,
+                    #[autospy(out)] d: &mut u8,
+                    #[autospy(out)] e: &mut u8,
+                );
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert!(actual.to_string().contains(
+            "compile_error ! { \"autospy supports at most 4 out arguments per function\" }"
+        ));
+    }
+
+    #[test]
+    fn borrowed_functions_pass_real_arguments_to_borrowed_return_function() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                #[autospy(borrowed)]
+                fn function(&self, #[autospy(ignore)] callback: &dyn Fn(u8), count: u8);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn function(&self, callback: &dyn Fn(u8), count: u8) {
+                    let return_value = self.function
+                        .returns
+                        .call_fn_mut(|getter| getter(callback, &count));
+                    match return_value {
+                        Some(return_value) => {
+                            self.function.capture(count);
+                            return_value
+                        }
+                        None => self.function.spy(count),
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -368,7 +888,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -388,7 +908,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -411,7 +931,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -435,7 +955,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -460,7 +980,39 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn default_trait_functions_marked_with_record_default_capture_arguments() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn one(&self) -> u8;
+                #[autospy(record_default)]
+                fn two(&self, argument: String, #[autospy(ignore)] ignored: &str) -> u8 {
+                    self.one() + argument.len() as u8 + ignored.len() as u8
+                }
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn one(&self) -> u8 {
+                    self.one.spy(())
+                }
+                #[track_caller]
+                fn two(&self, argument: String, ignored: &str) -> u8 {
+                    self.two.capture(Clone::clone(&argument));
+                    self.one() + argument.len() as u8 + ignored.len() as u8
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -483,7 +1035,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -506,7 +1058,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -522,7 +1074,7 @@ mod tests {
             impl<'a> Example<'a> for ExampleSpy<'a> {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -551,7 +1103,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -567,7 +1119,7 @@ mod tests {
             impl<T> Example<T> for ExampleSpy<T> {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -583,7 +1135,7 @@ mod tests {
             impl<T, R> Example<T, R> for ExampleSpy<T, R> {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -599,7 +1151,7 @@ mod tests {
             impl<T: Copy> Example<T> for ExampleSpy<T> {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -615,7 +1167,7 @@ mod tests {
             impl<T: Copy, P: Clone> Example<T,P> for ExampleSpy<T,P> {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -631,7 +1183,7 @@ mod tests {
             impl<T> Example<T> for ExampleSpy<T> where T: Copy {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -647,7 +1199,7 @@ mod tests {
             unsafe impl Example for ExampleSpy {}
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -670,7 +1222,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -705,7 +1257,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -745,7 +1297,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -792,7 +1344,7 @@ mod tests {
             }
         };
 
-        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new());
+        let actual = generate_spy_trait(&input, &AssociatedSpyTypes::new(), &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -823,7 +1375,7 @@ mod tests {
             },
         );
 
-        let actual = generate_spy_trait(&input, &associated_spy_types);
+        let actual = generate_spy_trait(&input, &associated_spy_types, &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -857,7 +1409,7 @@ mod tests {
             },
         );
 
-        let actual = generate_spy_trait(&input, &associated_spy_types);
+        let actual = generate_spy_trait(&input, &associated_spy_types, &Options::default());
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -891,7 +1443,86 @@ mod tests {
             },
         );
 
-        let actual = generate_spy_trait(&input, &associated_spy_types);
+        let actual = generate_spy_trait(&input, &associated_spy_types, &Options::default());
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn wrapping_functions_forward_to_inner_and_clone_owned_arguments() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {
+                fn function(&self, owned: String, #[autospy(ignore)] ignored: &str) -> u8;
+                fn mutable(&mut self);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                fn function(&self, owned: String, ignored: &str) -> u8 {
+                    match &self.autospy_inner {
+                        Some(autospy_inner) => self.function.spy_or_else(
+                            Clone::clone(&owned),
+                            || autospy_inner.function(owned, ignored)
+                        ),
+                        None => self.function.spy(owned),
+                    }
+                }
+                #[track_caller]
+                fn mutable(&mut self) {
+                    self.mutable.spy(())
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                wrapping: true,
+                ..Options::default()
+            },
+        );
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn wrapping_async_functions_forward_to_inner() {
+        let input: ItemTrait = parse_quote! {
+            #[async_trait]
+            trait Example {
+                async fn function(&self, argument: &str);
+            }
+        };
+
+        let expected = quote! {
+            #[cfg(test)]
+            #[async_trait]
+            impl Example for ExampleSpy {
+                #[track_caller]
+                async fn function(&self, argument: &str) {
+                    match &self.autospy_inner {
+                        Some(autospy_inner) => self.function.spy_async_or_else(
+                            argument.to_owned(),
+                            || autospy_inner.function(argument)
+                        ).await,
+                        None => self.function.spy_async(argument.to_owned()).await,
+                    }
+                }
+            }
+        };
+
+        let actual = generate_spy_trait(
+            &input,
+            &AssociatedSpyTypes::new(),
+            &Options {
+                wrapping: true,
+                ..Options::default()
+            },
+        );
 
         assert_eq!(actual.to_string(), expected.to_string());
     }
@@ -1,4 +1,113 @@
-use syn::{GenericParam, Generics, parse_quote};
+use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
+use crate::inspect;
+use proc_macro2::Ident;
+use std::collections::HashSet;
+use syn::visit::Visit;
+use syn::{GenericParam, Generics, ItemTrait, Lifetime, Type, WherePredicate, parse_quote};
+
+pub fn spy_generics_idents(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> Generics {
+    generics_idents(
+        &item_trait.generics,
+        inspect::has_function_returning_type_containing_elided_lifetime_reference(item_trait)
+            || associated_spy_types
+                .values()
+                .any(AssociatedType::has_lifetime),
+    )
+}
+
+pub fn generics_used_by<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+) -> Generics {
+    let mut used = UsedGenerics::default();
+    for r#type in types {
+        used.visit_type(r#type);
+    }
+
+    let (used_params, unused_params): (Vec<_>, Vec<_>) = generics
+        .params
+        .iter()
+        .cloned()
+        .partition(|param| used.contains(param));
+
+    let mut unused = UsedGenerics::default();
+    for param in &unused_params {
+        unused.insert(param);
+    }
+
+    let mut generics_used = generics.clone();
+    generics_used.params = used_params.into_iter().collect();
+
+    if generics_used.params.is_empty() {
+        generics_used.lt_token = None;
+        generics_used.gt_token = None;
+    }
+
+    if let Some(where_clause) = &mut generics_used.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| !unused.is_used_by(predicate))
+            .cloned()
+            .collect();
+    }
+
+    generics_used
+}
+
+#[derive(Default)]
+struct UsedGenerics {
+    idents: HashSet<Ident>,
+    lifetimes: HashSet<Lifetime>,
+}
+
+impl UsedGenerics {
+    fn insert(&mut self, param: &GenericParam) {
+        match param {
+            GenericParam::Type(type_param) => {
+                self.idents.insert(type_param.ident.clone());
+            }
+            GenericParam::Const(const_param) => {
+                self.idents.insert(const_param.ident.clone());
+            }
+            GenericParam::Lifetime(lifetime_param) => {
+                self.lifetimes.insert(lifetime_param.lifetime.clone());
+            }
+        }
+    }
+
+    fn contains(&self, param: &GenericParam) -> bool {
+        match param {
+            GenericParam::Type(type_param) => self.idents.contains(&type_param.ident),
+            GenericParam::Const(const_param) => self.idents.contains(&const_param.ident),
+            GenericParam::Lifetime(lifetime_param) => {
+                self.lifetimes.contains(&lifetime_param.lifetime)
+            }
+        }
+    }
+
+    fn is_used_by(&self, predicate: &WherePredicate) -> bool {
+        let mut used_by_predicate = Self::default();
+        used_by_predicate.visit_where_predicate(predicate);
+
+        !self.idents.is_disjoint(&used_by_predicate.idents)
+            || !self.lifetimes.is_disjoint(&used_by_predicate.lifetimes)
+    }
+}
+
+impl<'ast> Visit<'ast> for UsedGenerics {
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        self.idents.insert(segment.ident.clone());
+        syn::visit::visit_path_segment(self, segment);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.lifetimes.insert(lifetime.clone());
+    }
+}
 
 pub fn generics_idents(generics: &Generics, elided_lifetime: bool) -> Generics {
     let mut generics_idents = generics.clone();
@@ -23,8 +132,8 @@ fn clear_bounds(param: &mut GenericParam) {
 
 #[cfg(test)]
 mod tests {
-    use super::generics_idents;
-    use syn::{Generics, parse_quote};
+    use super::{generics_idents, generics_used_by};
+    use syn::{Generics, Type, parse_quote};
 
     #[test]
     fn single_bound() {
@@ -116,4 +225,53 @@ mod tests {
 
         assert_eq!(expected, generics_idents(&input, true));
     }
+
+    #[test]
+    fn used_generics_are_retained() {
+        let input: Generics = parse_quote! {
+            <T: Copy, U>
+        };
+
+        let used: Type = parse_quote! { Vec<T> };
+
+        let expected: Generics = parse_quote! {
+            <T: Copy>
+        };
+
+        assert_eq!(expected, generics_used_by(&input, [&used]));
+    }
+
+    #[test]
+    fn unused_generics_and_their_where_clauses_are_removed() {
+        let input: Generics = parse_quote! {
+            <'a, T, U, const N: usize>
+        };
+        let input = Generics {
+            where_clause: Some(parse_quote! { where T: Copy, U: Clone }),
+            ..input
+        };
+
+        let used: Type = parse_quote! { (&'a T, [u8; N]) };
+
+        let expected: Generics = parse_quote! {
+            <'a, T, const N: usize>
+        };
+        let expected = Generics {
+            where_clause: Some(parse_quote! { where T: Copy }),
+            ..expected
+        };
+
+        assert_eq!(expected, generics_used_by(&input, [&used]));
+    }
+
+    #[test]
+    fn no_generics_used() {
+        let input: Generics = parse_quote! {
+            <T>
+        };
+
+        let used: Type = parse_quote! { String };
+
+        assert_eq!(Generics::default(), generics_used_by(&input, [&used]));
+    }
 }
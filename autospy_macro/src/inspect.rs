@@ -1,3 +1,4 @@
+use crate::{arguments, attribute, supertraits};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::visit::Visit;
@@ -24,6 +25,33 @@ pub fn owned_trait_functions(item_trait: ItemTrait) -> impl Iterator<Item = Trai
     })
 }
 
+pub fn spy_functions(item_trait: &ItemTrait) -> impl Iterator<Item = TraitItemFn> {
+    trait_functions(item_trait)
+        .cloned()
+        .chain(supertraits::autospy_supertraits(item_trait).flat_map(owned_trait_functions))
+        .filter(has_spy_function)
+}
+
+pub fn has_spy_function(function: &TraitItemFn) -> bool {
+    !(attribute::has_use_default_attribute(&function.attrs) && function.default.is_some())
+}
+
+pub fn is_recorded_default_function(function: &TraitItemFn) -> bool {
+    attribute::has_record_default_attribute(&function.attrs) && function.default.is_some()
+}
+
+pub fn has_spy_arguments(function: &TraitItemFn) -> bool {
+    arguments::spy_arguments(function).next().is_some()
+}
+
+pub fn is_borrowed_function(function: &TraitItemFn) -> bool {
+    attribute::has_borrowed_attribute(&function.attrs)
+}
+
+pub fn has_out_arguments(function: &TraitItemFn) -> bool {
+    arguments::out_arguments(function).next().is_some()
+}
+
 pub fn cfg() -> TokenStream {
     if cfg!(feature = "test") {
         quote! { #[cfg(test)] }
@@ -3,17 +3,27 @@ extern crate proc_macro;
 mod arguments;
 mod associated_types;
 mod attribute;
+mod conditional_derive;
 mod edit;
 mod generate;
+mod generate_spy_arguments;
+mod generate_spy_borrowed;
+mod generate_spy_calls;
 mod generate_spy_default;
+mod generate_spy_impl;
+mod generate_spy_serialize;
 mod generate_spy_struct;
 mod generate_spy_trait;
 mod generics;
 mod inspect;
+mod options;
+mod owned;
 mod strip_attributes;
 mod supertraits;
+mod wrapping;
 
 use generate::generate;
+use options::Options;
 use proc_macro::TokenStream;
 use syn::parse_quote;
 
@@ -21,10 +31,6 @@ use syn::parse_quote;
 #[proc_macro_attribute]
 pub fn autospy(attributes: TokenStream, item: TokenStream) -> TokenStream {
     let item = proc_macro2::TokenStream::from(item);
-    let external_trait = match attributes.to_string().as_str() {
-        "" => false,
-        "external" => true,
-        _ => panic!("invalid attribute"),
-    };
-    TokenStream::from(generate(&parse_quote! { #item }, external_trait))
+    let options = Options::parse(attributes.into());
+    TokenStream::from(generate(&parse_quote! { #item }, &options))
 }
@@ -0,0 +1,104 @@
+use proc_macro2::TokenStream;
+use syn::parse::Parser;
+use syn::{Ident, Token, punctuated::Punctuated};
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub external: bool,
+    pub calls: bool,
+    pub nice: bool,
+    pub named_arguments: bool,
+    pub wrapping: bool,
+    pub recording: bool,
+    pub serialize: bool,
+}
+
+impl Options {
+    pub fn parse(attributes: TokenStream) -> Self {
+        Punctuated::<Ident, Token![,]>::parse_terminated
+            .parse2(attributes)
+            .expect("invalid attribute")
+            .iter()
+            .fold(Self::default(), Self::with_option)
+    }
+
+    fn with_option(mut self, option: &Ident) -> Self {
+        match option.to_string().as_str() {
+            "external" => self.external = true,
+            "calls" => self.calls = true,
+            "nice" => self.nice = true,
+            "named_arguments" => self.named_arguments = true,
+            "wrapping" => self.wrapping = true,
+            "recording" => {
+                self.wrapping = true;
+                self.recording = true;
+            }
+            "serialize" => self.serialize = true,
+            _ => panic!("invalid attribute"),
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use quote::quote;
+
+    #[test]
+    fn no_options() {
+        assert_eq!(Options::default(), Options::parse(quote! {}));
+    }
+
+    #[test]
+    fn external_option() {
+        let expected = Options {
+            external: true,
+            ..Options::default()
+        };
+
+        assert_eq!(expected, Options::parse(quote! { external }));
+    }
+
+    #[test]
+    fn multiple_options() {
+        let expected = Options {
+            external: true,
+            calls: true,
+            nice: true,
+            named_arguments: true,
+            wrapping: true,
+            recording: false,
+            serialize: true,
+        };
+
+        assert_eq!(
+            expected,
+            Options::parse(quote! { external, calls, nice, named_arguments, wrapping, serialize })
+        );
+    }
+
+    #[test]
+    fn recording_option_implies_wrapping() {
+        let expected = Options {
+            wrapping: true,
+            recording: true,
+            ..Options::default()
+        };
+
+        assert_eq!(expected, Options::parse(quote! { recording }));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attribute")]
+    fn unknown_option() {
+        let _ = Options::parse(quote! { unknown });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attribute")]
+    fn non_ident_option() {
+        let _ = Options::parse(quote! { "external" });
+    }
+}
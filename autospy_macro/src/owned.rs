@@ -0,0 +1,245 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit::Visit;
+use syn::{
+    GenericArgument, PathArguments, Type, TypeArray, TypePath, TypeReference, TypeSlice, TypeTuple,
+    parse_quote,
+};
+
+pub fn contains_reference(r#type: &Type) -> bool {
+    let mut visitor = ReferenceVisitor {
+        found_reference: false,
+    };
+    visitor.visit_type(r#type);
+    visitor.found_reference
+}
+
+pub fn owned_type(r#type: &Type) -> Type {
+    if !contains_reference(r#type) {
+        return r#type.clone();
+    }
+
+    match r#type {
+        Type::Reference(TypeReference { elem, .. }) if contains_reference(elem) => owned_type(elem),
+        Type::Reference(TypeReference { elem, .. }) => parse_quote! { <#elem as ToOwned>::Owned },
+        Type::Paren(paren) => owned_type(&paren.elem),
+        Type::Slice(TypeSlice { elem, .. }) => {
+            let owned_elem = owned_type(elem);
+            parse_quote! { Vec<#owned_elem> }
+        }
+        Type::Array(TypeArray { elem, len, .. }) => {
+            let owned_elem = owned_type(elem);
+            parse_quote! { [#owned_elem; #len] }
+        }
+        Type::Tuple(TypeTuple { elems, .. }) => {
+            let owned_elems = elems.iter().map(owned_type);
+            parse_quote! { (#(#owned_elems,)*) }
+        }
+        Type::Path(type_path) => match owned_container(type_path) {
+            Some((container, elem)) => {
+                let owned_elem = owned_type(elem);
+                parse_quote! { #container<#owned_elem> }
+            }
+            None => r#type.clone(),
+        },
+        _ => r#type.clone(),
+    }
+}
+
+pub fn to_owned_expression(r#type: &Type, reference: &TokenStream) -> syn::Result<TokenStream> {
+    if !contains_reference(r#type) {
+        return Ok(quote! { #reference.clone() });
+    }
+
+    Ok(match r#type {
+        Type::Reference(TypeReference { elem, .. }) if contains_reference(elem) => {
+            to_owned_expression(elem, &quote! { (*#reference) })?
+        }
+        Type::Reference(_) => quote! { (*#reference).to_owned() },
+        Type::Paren(paren) => to_owned_expression(&paren.elem, reference)?,
+        Type::Slice(TypeSlice { elem, .. }) => {
+            let owned_value = to_owned_expression(elem, &quote! { value })?;
+            quote! { #reference.iter().map(|value| #owned_value).collect::<Vec<_>>() }
+        }
+        Type::Array(TypeArray { elem, .. }) => {
+            let owned_value = to_owned_expression(elem, &quote! { value })?;
+            quote! { #reference.each_ref().map(|value| #owned_value) }
+        }
+        Type::Tuple(TypeTuple { elems, .. }) => {
+            let owned_elems = elems
+                .iter()
+                .enumerate()
+                .map(|(index, elem)| {
+                    let index = syn::Index::from(index);
+                    to_owned_expression(elem, &quote! { (&#reference.#index) })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { (#(#owned_elems,)*) }
+        }
+        Type::Path(type_path) => match owned_container(type_path) {
+            Some((container, elem)) if container == "Option" => {
+                let owned_value = to_owned_expression(elem, &quote! { value })?;
+                quote! { #reference.as_ref().map(|value| #owned_value) }
+            }
+            Some((_, elem)) => {
+                let owned_value = to_owned_expression(elem, &quote! { value })?;
+                quote! { #reference.iter().map(|value| #owned_value).collect::<Vec<_>>() }
+            }
+            None => return Err(unconvertible_type_error(r#type)),
+        },
+        _ => return Err(unconvertible_type_error(r#type)),
+    })
+}
+
+fn unconvertible_type_error(r#type: &Type) -> syn::Error {
+    syn::Error::new_spanned(
+        r#type,
+        "autospy cannot convert this type to an owned type, use `#[autospy(ignore)]` or \
+         `#[autospy(into = \"TYPE\", with = \"FUNCTION\")]` on the argument",
+    )
+}
+
+fn owned_container(type_path: &TypePath) -> Option<(&syn::Ident, &Type)> {
+    if type_path.qself.is_some() || type_path.path.segments.len() != 1 {
+        return None;
+    }
+
+    let segment = &type_path.path.segments[0];
+    if segment.ident != "Option" && segment.ident != "Vec" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first() {
+        Some(GenericArgument::Type(elem)) if arguments.args.len() == 1 => {
+            Some((&segment.ident, elem))
+        }
+        _ => None,
+    }
+}
+
+struct ReferenceVisitor {
+    found_reference: bool,
+}
+
+impl<'ast> Visit<'ast> for ReferenceVisitor {
+    fn visit_type_reference(&mut self, _: &'ast TypeReference) {
+        self.found_reference = true;
+    }
+
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {}
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contains_reference, owned_type, to_owned_expression};
+    use quote::quote;
+    use syn::{Type, parse_quote};
+
+    #[test]
+    fn types_without_references() {
+        let input: Type = parse_quote! { Option<Vec<String>> };
+
+        assert!(!contains_reference(&input));
+        assert_eq!(input, owned_type(&input));
+    }
+
+    #[test]
+    fn function_pointer_references_are_not_converted() {
+        let input: Type = parse_quote! { fn(&str) -> bool };
+
+        assert!(!contains_reference(&input));
+    }
+
+    #[test]
+    fn option_of_reference() {
+        let input: Type = parse_quote! { Option<&str> };
+
+        let expected: Type = parse_quote! { Option<<str as ToOwned>::Owned> };
+
+        assert_eq!(expected, owned_type(&input));
+        assert_eq!(
+            quote! { (&argument).as_ref().map(|value| (*value).to_owned()) }.to_string(),
+            to_owned_expression(&input, &quote! { (&argument) })
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn slice_of_references() {
+        let input: Type = parse_quote! { [&str] };
+
+        let expected: Type = parse_quote! { Vec<<str as ToOwned>::Owned> };
+
+        assert_eq!(expected, owned_type(&input));
+        assert_eq!(
+            quote! { argument.iter().map(|value| (*value).to_owned()).collect::<Vec<_>>() }
+                .to_string(),
+            to_owned_expression(&input, &quote! { argument })
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn tuple_of_references_and_owned_values() {
+        let input: Type = parse_quote! { (&Path, u8) };
+
+        let expected: Type = parse_quote! { (<Path as ToOwned>::Owned, u8,) };
+
+        assert_eq!(expected, owned_type(&input));
+        assert_eq!(
+            quote! { ((*(&argument.0)).to_owned(), (&argument.1).clone(),) }.to_string(),
+            to_owned_expression(&input, &quote! { argument })
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn array_of_references() {
+        let input: Type = parse_quote! { [&u8; 2] };
+
+        let expected: Type = parse_quote! { [<u8 as ToOwned>::Owned; 2] };
+
+        assert_eq!(expected, owned_type(&input));
+        assert_eq!(
+            quote! { argument.each_ref().map(|value| (*value).to_owned()) }.to_string(),
+            to_owned_expression(&input, &quote! { argument })
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn nested_references() {
+        let input: Type = parse_quote! { Vec<Option<&&str>> };
+
+        let expected: Type = parse_quote! { Vec<Option<<str as ToOwned>::Owned>> };
+
+        assert_eq!(expected, owned_type(&input));
+    }
+
+    #[test]
+    fn unknown_containers_of_references_are_not_converted() {
+        let input: Type = parse_quote! { HashMap<&str, u8> };
+
+        assert_eq!(input, owned_type(&input));
+        assert!(to_owned_expression(&input, &quote! { argument }).is_err());
+    }
+
+    #[test]
+    fn references_nested_in_unknown_containers_are_not_converted() {
+        let input: Type = parse_quote! { Option<Box<&str>> };
+
+        assert!(to_owned_expression(&input, &quote! { argument }).is_err());
+    }
+}
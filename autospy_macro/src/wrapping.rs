@@ -0,0 +1,118 @@
+use crate::associated_types::AssociatedSpyTypes;
+use crate::options::Options;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, GenericParam, ItemTrait, Pat, TraitItemFn};
+
+pub fn wrapped_trait(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> TokenStream {
+    let trait_name = &item_trait.ident;
+    let generic_arguments = item_trait.generics.params.iter().map(|param| match param {
+        GenericParam::Type(type_param) => {
+            let ident = &type_param.ident;
+            quote! { #ident }
+        }
+        GenericParam::Lifetime(lifetime_param) => {
+            let lifetime = &lifetime_param.lifetime;
+            quote! { #lifetime }
+        }
+        GenericParam::Const(const_param) => {
+            let ident = &const_param.ident;
+            quote! { #ident }
+        }
+    });
+    let associated_type_bindings = associated_spy_types
+        .iter()
+        .filter(|(_, associated_type)| associated_type.generics.params.is_empty())
+        .map(|(name, associated_type)| {
+            let r#type = &associated_type.r#type;
+            quote! { #name = #r#type }
+        });
+    let arguments: Vec<_> = generic_arguments.chain(associated_type_bindings).collect();
+
+    if arguments.is_empty() {
+        quote! { #trait_name }
+    } else {
+        quote! { #trait_name<#(#arguments),*> }
+    }
+}
+
+pub fn inner_type(
+    item_trait: &ItemTrait,
+    associated_spy_types: &AssociatedSpyTypes,
+) -> TokenStream {
+    let wrapped_trait = wrapped_trait(item_trait, associated_spy_types);
+    quote! { std::sync::Arc<dyn #wrapped_trait + Send + Sync> }
+}
+
+pub fn is_forwarded_function(function: &TraitItemFn, options: &Options) -> bool {
+    options.wrapping
+        && function
+            .sig
+            .receiver()
+            .is_some_and(|receiver| receiver.reference.is_some() && receiver.mutability.is_none())
+}
+
+pub fn forward_call(function: &TraitItemFn) -> TokenStream {
+    let function_name = &function.sig.ident;
+    let arguments = function.sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(argument) => match &*argument.pat {
+            Pat::Ident(pat_ident) => Some(&pat_ident.ident),
+            _ => panic!("wrapped function arguments must be named"),
+        },
+        FnArg::Receiver(_) => None,
+    });
+    let call = quote! { autospy_inner.#function_name(#(#arguments),*) };
+
+    if function.sig.unsafety.is_some() {
+        quote! { unsafe { #call } }
+    } else {
+        call
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrapped_trait;
+    use crate::associated_types::{AssociatedSpyTypes, AssociatedType};
+    use quote::quote;
+    use syn::{ItemTrait, parse_quote};
+
+    #[test]
+    fn wrapped_trait_without_generics() {
+        let input: ItemTrait = parse_quote! {
+            trait Example {}
+        };
+
+        let actual = wrapped_trait(&input, &AssociatedSpyTypes::new());
+
+        assert_eq!(quote! { Example }.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn wrapped_trait_binds_generics_and_associated_types() {
+        let input: ItemTrait = parse_quote! {
+            trait Example<'a, T: Copy> {
+                type Item;
+            }
+        };
+
+        let mut associated_types = AssociatedSpyTypes::new();
+        associated_types.insert(
+            parse_quote! { Item },
+            AssociatedType {
+                r#type: parse_quote! { String },
+                generics: parse_quote! {},
+            },
+        );
+
+        let actual = wrapped_trait(&input, &associated_types);
+
+        assert_eq!(
+            quote! { Example<'a, T, Item = String> }.to_string(),
+            actual.to_string()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::Arguments;
+use std::fmt::Debug;
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_called<A: Debug>(arguments: &Arguments<A>, times: Option<usize>) {
+    let captured = arguments.get();
+    let is_met = times.map_or(!captured.is_empty(), |times| captured.len() == times);
+    if is_met {
+        return;
+    }
+
+    let expected = times.map_or_else(
+        || "called".to_string(),
+        |times| format!("called {times} time(s)"),
+    );
+    let message = format!(
+        "function '{}' was expected to be {}, but was called {} time(s){}",
+        arguments.name(),
+        expected,
+        captured.len(),
+        calls(&captured)
+    );
+    drop(captured);
+    panic!("{message}");
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_not_called<A: Debug>(arguments: &Arguments<A>) {
+    let captured = arguments.get();
+    if captured.is_empty() {
+        return;
+    }
+
+    let message = format!(
+        "function '{}' was expected not to be called, but was called {} time(s){}",
+        arguments.name(),
+        captured.len(),
+        calls(&captured)
+    );
+    drop(captured);
+    panic!("{message}");
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_called_with<A: Debug, B: PartialEq<A> + Debug>(
+    arguments: &Arguments<A>,
+    expected: &B,
+) {
+    let captured = arguments.get();
+    let Some((last, previous)) = captured.split_last() else {
+        let message = format!(
+            "function '{}' was expected to be called with {:?}, but was not called",
+            arguments.name(),
+            expected
+        );
+        drop(captured);
+        panic!("{message}");
+    };
+    if expected == last {
+        return;
+    }
+
+    let message = format!(
+        "function '{}' was not last called with the expected arguments (- expected, + actual):{}{}{}",
+        arguments.name(),
+        calls(previous),
+        call(REMOVED, previous.len(), expected),
+        call(ADDED, previous.len(), last)
+    );
+    drop(captured);
+    panic!("{message}");
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_calls<A: Debug, B: PartialEq<A> + Debug>(
+    arguments: &Arguments<A>,
+    expected: impl AsRef<[B]>,
+) {
+    let expected = expected.as_ref();
+    let captured = arguments.get();
+    if expected.len() == captured.len()
+        && expected
+            .iter()
+            .zip(captured.iter())
+            .all(|(expected, actual)| expected == actual)
+    {
+        return;
+    }
+
+    let message = format!(
+        "function '{}' was not called with the expected arguments (- expected, + actual):{}",
+        arguments.name(),
+        diff(expected, &captured)
+    );
+    drop(captured);
+    panic!("{message}");
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_any_call<A: Debug, B: PartialEq<A> + Debug>(arguments: &Arguments<A>, expected: &B) {
+    let captured = arguments.get();
+    if captured.iter().any(|actual| expected == actual) {
+        return;
+    }
+
+    let message = format!(
+        "function '{}' was never called with {:?}, but was called {} time(s){}",
+        arguments.name(),
+        expected,
+        captured.len(),
+        calls(&captured)
+    );
+    drop(captured);
+    panic!("{message}");
+}
+
+const UNCHANGED: char = ' ';
+const REMOVED: char = '-';
+const ADDED: char = '+';
+
+fn call(marker: char, index: usize, arguments: &dyn Debug) -> String {
+    format!("\n  {marker}[{index}] {arguments:?}")
+}
+
+fn calls<A: Debug>(captured: &[A]) -> String {
+    captured
+        .iter()
+        .enumerate()
+        .map(|(index, arguments)| call(UNCHANGED, index, arguments))
+        .collect()
+}
+
+fn diff<A: Debug, B: PartialEq<A> + Debug>(expected: &[B], actual: &[A]) -> String {
+    (0..expected.len().max(actual.len()))
+        .map(|index| match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => call(UNCHANGED, index, actual),
+            (expected, actual) => {
+                let removed = expected.map(|expected| call(REMOVED, index, expected));
+                let added = actual.map(|actual| call(ADDED, index, actual));
+                removed.into_iter().chain(added).collect()
+            }
+        })
+        .collect()
+}
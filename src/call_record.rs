@@ -0,0 +1,52 @@
+use std::panic::Location;
+use std::thread::Thread;
+use std::time::Instant;
+
+/// Where, when and on which thread a spy function was called.
+///
+/// The location is that of the code calling the spy function. For async functions the caller cannot be tracked
+/// through the returned future, so the location is that of the spy.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait: Send + Sync + 'static {
+///     fn foo(&self);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set_fn(|_| ());
+///
+/// std::thread::Builder::new()
+///     .name("worker".to_string())
+///     .spawn({
+///         let spy = spy.clone();
+///         move || spy.foo()
+///     })
+///     .unwrap()
+///     .join()
+///     .unwrap();
+///
+/// let call = &spy.foo.arguments.calls()[0];
+/// assert_eq!(Some("worker"), call.thread.name());
+/// println!("foo called at {} after {:?}", call.location, call.timestamp.elapsed());
+/// ```
+#[derive(Clone, Debug)]
+pub struct CallRecord {
+    /// The location of the code which called the spy function.
+    pub location: &'static Location<'static>,
+    /// The thread which called the spy function.
+    pub thread: Thread,
+    /// When the spy function was called.
+    pub timestamp: Instant,
+}
+
+impl CallRecord {
+    pub(crate) fn new(location: &'static Location<'static>) -> Self {
+        Self {
+            location,
+            thread: std::thread::current(),
+            timestamp: Instant::now(),
+        }
+    }
+}
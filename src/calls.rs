@@ -0,0 +1,165 @@
+use crate::macros::impl_slice_partial_eq;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+///
+/// Calls implements [`PartialEq`] for `[C]`, `&[C]` and `Vec<C>`.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy(calls)]
+/// trait MyTrait {
+///     fn open(&self);
+///     fn write(&self, bytes: &[u8]);
+/// }
+///
+/// fn use_trait(trait_object: &impl MyTrait) {
+///     trait_object.open();
+///     trait_object.write(&[1, 2]);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.open.returns.set([()]);
+/// spy.write.returns.set([()]);
+///
+/// use_trait(&spy);
+///
+/// // all valid PartialEq implementations
+/// let expected = [MyTraitSpyCall::Open, MyTraitSpyCall::Write(vec![1, 2])];
+/// assert_eq!(expected, spy.autospy_calls());
+/// assert_eq!(expected.as_slice(), spy.autospy_calls());
+/// assert_eq!(expected.to_vec(), spy.autospy_calls());
+/// ```
+pub struct Calls<C> {
+    recorded: Arc<Mutex<Vec<C>>>,
+}
+
+impl<C: Debug> Debug for Calls<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.recorded.lock() {
+            Ok(recorded) => recorded.fmt(f),
+            Err(_) => write!(f, "mutex poisoned"),
+        }
+    }
+}
+
+impl<C> Clone for Calls<C> {
+    fn clone(&self) -> Self {
+        Self {
+            recorded: self.recorded.clone(),
+        }
+    }
+}
+
+impl<C> Default for Calls<C> {
+    fn default() -> Self {
+        Self {
+            recorded: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl_slice_partial_eq!(Calls);
+
+impl<C> Calls<C> {
+    #[doc(hidden)]
+    pub fn record(&self, call: C) {
+        self.recorded.lock().expect("mutex poisoned").push(call);
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub fn interactions(&self, format_call: Option<fn(&C) -> String>) -> String {
+        let count = self.get().len();
+        if count == 0 {
+            return "calls: none".to_string();
+        }
+        let Some(format_call) = format_call else {
+            return format!("calls: {count} call(s), not Debug");
+        };
+        let calls: Vec<_> = self
+            .get()
+            .iter()
+            .enumerate()
+            .map(|(index, call)| format!("[{index}] {}", format_call(call)))
+            .collect();
+        format!("calls:\n  {}", calls.join("\n  "))
+    }
+
+    /// Gets the recorded calls. This returns a [`MutexGuard`] which must be dereferenced.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy(calls)]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// // get will not clear the recorded calls
+    /// assert_eq!(vec![MyTraitSpyCall::Foo(10)], *spy.autospy_calls().get());
+    /// assert_eq!(vec![MyTraitSpyCall::Foo(10)], *spy.autospy_calls().get());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get(&self) -> MutexGuard<'_, Vec<C>> {
+        self.recorded.lock().expect("mutex poisoned")
+    }
+
+    /// Takes the recorded calls.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy(calls)]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// // take will clear the recorded calls
+    /// assert_eq!(vec![MyTraitSpyCall::Foo(10)], spy.autospy_calls().take());
+    /// assert!(spy.autospy_calls().take().is_empty());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn take(&self) -> Vec<C> {
+        std::mem::take(&mut *self.get())
+    }
+
+    /// Clears the recorded calls.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy(calls)]
+    /// trait MyTrait {
+    ///     fn foo(&self);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// spy.foo();
+    /// spy.autospy_calls().clear();
+    ///
+    /// assert!(spy.autospy_calls().get().is_empty());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn clear(&self) {
+        self.get().clear();
+    }
+}
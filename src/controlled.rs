@@ -0,0 +1,94 @@
+/// Controls the responses of an async spy function, obtained from
+/// [`controlled()`](crate::Returns::controlled).
+///
+/// Each call to the spy function stays pending until the test responds to it.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// #[async_trait::async_trait]
+/// trait Client: Send + Sync + 'static {
+///     async fn fetch(&self, url: &str) -> String;
+/// }
+///
+/// tokio::runtime::Runtime::new().unwrap().block_on(async {
+///     let spy = ClientSpy::default();
+///     let controller = spy.fetch.returns.controlled();
+///
+///     let client = spy.clone();
+///     let request = tokio::task::spawn(async move { client.fetch("/users").await });
+///
+///     let call = controller.next_call().await;
+///     assert_eq!("/users", call.arguments());
+///     call.respond("alice".to_string());
+///
+///     assert_eq!("alice", request.await.unwrap());
+/// })
+/// ```
+pub struct Controller<A, R> {
+    receiver: async_channel::Receiver<PendingCall<A, R>>,
+}
+
+impl<A, R> Controller<A, R> {
+    pub(crate) const fn new(receiver: async_channel::Receiver<PendingCall<A, R>>) -> Self {
+        Self { receiver }
+    }
+
+    /// Waits for the next call to the spy function.
+    ///
+    /// # Panics
+    /// Panics if the spy function has been dropped.
+    pub async fn next_call(&self) -> PendingCall<A, R> {
+        self.receiver.recv().await.expect("spy function dropped")
+    }
+
+    /// Gets the next call to the spy function, if one has been made.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Client: Send + Sync + 'static {
+    ///     async fn fetch(&self, url: &str) -> String;
+    /// }
+    ///
+    /// let spy = ClientSpy::default();
+    /// let controller = spy.fetch.returns.controlled();
+    ///
+    /// assert!(controller.try_next_call().is_none());
+    /// ```
+    #[must_use]
+    pub fn try_next_call(&self) -> Option<PendingCall<A, R>> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// A call to a controlled async spy function that is waiting for a response.
+///
+/// The arguments are captured when the call is made, and the pending call holds a copy of them.
+/// Dropping a pending call without responding panics the caller.
+pub struct PendingCall<A, R> {
+    arguments: A,
+    responder: async_channel::Sender<R>,
+}
+
+impl<A, R> PendingCall<A, R> {
+    pub(crate) const fn new(arguments: A, responder: async_channel::Sender<R>) -> Self {
+        Self {
+            arguments,
+            responder,
+        }
+    }
+
+    /// The arguments of the call.
+    #[must_use]
+    pub const fn arguments(&self) -> &A {
+        &self.arguments
+    }
+
+    /// Responds to the call, returning `value` from the spy function.
+    /// If the caller has stopped waiting, for example after a timeout, the value is discarded.
+    pub fn respond(self, value: R) {
+        let _ = self.responder.try_send(value);
+    }
+}
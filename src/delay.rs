@@ -0,0 +1,51 @@
+use crate::timer;
+use std::collections::VecDeque;
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+#[derive(Default)]
+pub struct Delays {
+    queue: VecDeque<Duration>,
+    all: Option<Duration>,
+}
+
+impl Delays {
+    pub fn set<I: IntoIterator<Item = Duration>>(&mut self, delays: I) {
+        self.queue = delays.into_iter().collect();
+    }
+
+    pub const fn set_all(&mut self, delay: Duration) {
+        self.all = Some(delay);
+    }
+
+    pub fn next(&mut self) -> Option<Duration> {
+        self.queue.pop_front().or(self.all)
+    }
+
+    pub const fn all(&self) -> Option<Duration> {
+        self.all
+    }
+}
+
+/// Sleeps on tokio's timer inside a tokio runtime, so paused time is respected, and on the
+/// shared autospy timer everywhere else.
+pub async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return tokio::time::sleep(duration).await;
+    }
+    timer::sleep(duration).await;
+}
+
+pub async fn timeout<T>(duration: Duration, future: impl Future<Output = T>) -> Option<T> {
+    let mut future = pin!(future);
+    let mut sleep = pin!(sleep(duration));
+    std::future::poll_fn(|context| {
+        if let Poll::Ready(value) = future.as_mut().poll(context) {
+            return Poll::Ready(Some(value));
+        }
+        sleep.as_mut().poll(context).map(|()| None)
+    })
+    .await
+}
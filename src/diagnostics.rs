@@ -0,0 +1,159 @@
+use crate::{Calls, SpyFunction};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::panic::Location;
+
+pub struct Diagnostics<A, R> {
+    trait_name: Option<&'static str>,
+    qualified_name: Option<&'static str>,
+    format_arguments: Option<fn(&A) -> String>,
+    format_return: Option<fn(&R) -> String>,
+}
+
+impl<A, R> Default for Diagnostics<A, R> {
+    fn default() -> Self {
+        Self {
+            trait_name: None,
+            qualified_name: None,
+            format_arguments: None,
+            format_return: None,
+        }
+    }
+}
+
+impl<A, R> Diagnostics<A, R> {
+    pub const fn new(
+        trait_name: &'static str,
+        qualified_name: &'static str,
+        format_arguments: Option<fn(&A) -> String>,
+        format_return: Option<fn(&R) -> String>,
+    ) -> Self {
+        Self {
+            trait_name: Some(trait_name),
+            qualified_name: Some(qualified_name),
+            format_arguments,
+            format_return,
+        }
+    }
+
+    pub const fn qualified_name(&self) -> Option<&'static str> {
+        self.qualified_name
+    }
+
+    pub fn details(
+        &self,
+        set_location: Option<&Location<'_>>,
+        unused_returns: &[&R],
+        arguments: &[A],
+    ) -> String {
+        let mut details = Vec::new();
+        if let Some(trait_name) = self.trait_name {
+            details.push(format!("trait: {trait_name}"));
+        }
+        if let Some(set_location) = set_location {
+            details.push(format!("return values set at: {set_location}"));
+        }
+        if !unused_returns.is_empty() {
+            let unused_returns = format_values(self.format_return, unused_returns.iter().copied());
+            details.push(format!("unused return values: {unused_returns}"));
+        }
+        let arguments = format_values(self.format_arguments, arguments.iter());
+        details.push(format!("captured arguments: {arguments}"));
+        format!("\n  {}", details.join("\n  "))
+    }
+
+    pub fn interactions(&self, name: &str, arguments: &[A]) -> String {
+        if arguments.is_empty() {
+            return format!("{name}: not called");
+        }
+        let Some(format) = self.format_arguments else {
+            return format!("{name}: called {} time(s), not Debug", arguments.len());
+        };
+        let calls: Vec<_> = arguments
+            .iter()
+            .enumerate()
+            .map(|(index, arguments)| format!("[{index}] {}", format(arguments)))
+            .collect();
+        format!("{name}:\n  {}", calls.join("\n  "))
+    }
+}
+
+fn format_values<'a, T: 'a>(
+    format: Option<fn(&T) -> String>,
+    values: impl ExactSizeIterator<Item = &'a T>,
+) -> String {
+    match format {
+        Some(format) => format!("[{}]", values.map(format).collect::<Vec<_>>().join(", ")),
+        None => format!("{} value(s), not Debug", values.len()),
+    }
+}
+
+#[doc(hidden)]
+pub struct DebugProbe<T>(PhantomData<fn(&T)>);
+
+impl<T> DebugProbe<T> {
+    #[must_use]
+    pub const fn arguments_of<R, H: ?Sized>(_: &SpyFunction<T, R, H>) -> Self {
+        Self(PhantomData)
+    }
+
+    #[must_use]
+    pub const fn return_of<A, H: ?Sized>(_: &SpyFunction<A, T, H>) -> Self {
+        Self(PhantomData)
+    }
+
+    #[must_use]
+    pub const fn calls_of(_: &Calls<T>) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait FormatWithDebug<T> {
+    fn formatter(&self) -> Option<fn(&T) -> String>;
+}
+
+impl<T: Debug> FormatWithDebug<T> for DebugProbe<T> {
+    fn formatter(&self) -> Option<fn(&T) -> String> {
+        Some(|value| format!("{value:?}"))
+    }
+}
+
+#[doc(hidden)]
+pub trait FormatWithoutDebug<T> {
+    fn formatter(&self) -> Option<fn(&T) -> String>;
+}
+
+impl<T> FormatWithoutDebug<T> for &DebugProbe<T> {
+    fn formatter(&self) -> Option<fn(&T) -> String> {
+        None
+    }
+}
+
+/// Sets the diagnostics of a spy function, formatting its arguments and return values with
+/// [`Debug`] when they implement it.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __diagnostics {
+    ($spy_function:expr, $trait_name:literal, $function_name:literal) => {{
+        #[allow(unused_imports)]
+        use $crate::{FormatWithDebug as _, FormatWithoutDebug as _};
+        $spy_function.set_diagnostics(
+            $trait_name,
+            concat!($trait_name, "::", $function_name),
+            (&$crate::DebugProbe::arguments_of(&$spy_function)).formatter(),
+            (&$crate::DebugProbe::return_of(&$spy_function)).formatter(),
+        );
+    }};
+}
+
+/// Renders the calls of a spy, formatting them with [`Debug`] when they implement it.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __calls_interactions {
+    ($calls:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{FormatWithDebug as _, FormatWithoutDebug as _};
+        $calls.interactions((&$crate::DebugProbe::calls_of(&$calls)).formatter())
+    }};
+}
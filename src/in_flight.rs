@@ -0,0 +1,103 @@
+use std::sync::{Arc, Mutex};
+
+/// The outcome of a call to an async spy function.
+///
+/// # Examples
+/// ```rust
+/// use autospy::CallOutcome;
+/// use pollster::FutureExt as _;
+///
+/// #[autospy::autospy]
+/// #[async_trait::async_trait]
+/// trait Service {
+///     async fn fetch(&self) -> u8;
+/// }
+///
+/// let spy = ServiceSpy::default();
+/// spy.fetch.returns.set([1]);
+///
+/// assert_eq!(1, spy.fetch().block_on());
+/// assert_eq!([CallOutcome::Completed], spy.fetch.call_outcomes().as_slice());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallOutcome {
+    /// The call has not finished yet.
+    InFlight,
+    /// The call ran to completion.
+    Completed,
+    /// The future of the call was dropped before it completed.
+    Cancelled,
+}
+
+#[derive(Default)]
+pub struct InFlightCalls {
+    in_flight: usize,
+    max_concurrent: usize,
+    outcomes: Vec<CallOutcome>,
+    cleared: usize,
+}
+
+impl InFlightCalls {
+    pub const fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    pub const fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn outcomes(&self) -> Vec<CallOutcome> {
+        self.outcomes.clone()
+    }
+
+    pub fn clear(&mut self) {
+        self.max_concurrent = self.in_flight;
+        self.cleared += self.outcomes.len();
+        self.outcomes.clear();
+    }
+}
+
+pub struct InFlightCall {
+    calls: Arc<Mutex<InFlightCalls>>,
+    index: usize,
+    completed: bool,
+}
+
+impl InFlightCall {
+    pub fn start(calls: &Arc<Mutex<InFlightCalls>>) -> Self {
+        let mut guard = calls.lock().expect("mutex poisoned");
+        guard.in_flight += 1;
+        guard.max_concurrent = guard.max_concurrent.max(guard.in_flight);
+        let index = guard.cleared + guard.outcomes.len();
+        guard.outcomes.push(CallOutcome::InFlight);
+        drop(guard);
+
+        Self {
+            calls: Arc::clone(calls),
+            index,
+            completed: false,
+        }
+    }
+
+    pub fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        let Ok(mut calls) = self.calls.lock() else {
+            return;
+        };
+        calls.in_flight -= 1;
+        if let Some(index) = self.index.checked_sub(calls.cleared)
+            && let Some(outcome) = calls.outcomes.get_mut(index)
+        {
+            *outcome = if self.completed {
+                CallOutcome::Completed
+            } else {
+                CallOutcome::Cancelled
+            };
+        }
+    }
+}
@@ -81,6 +81,29 @@
 //! assert_eq!(["hello!"], spy.foo.arguments);
 //! ```
 //!
+//! References nested inside slices, arrays, tuples, [`Option`] and [`Vec`] are converted too.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, name: Option<&str>, tags: &[&str]);
+//! }
+//!
+//! fn use_trait(x: &impl MyTrait) {
+//!     x.foo(Some("hello!"), &["a", "b"])
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([()]);
+//!
+//! use_trait(&spy);
+//!
+//! assert_eq!(
+//!     [(Some("hello!".to_string()), vec!["a".to_string(), "b".to_string()])],
+//!     spy.foo.arguments
+//! );
+//! ```
+//!
 //! ## Reference returns
 //!
 //! Functions that return non-mutable and mutable references are supported. If an explicit lifetime
@@ -102,6 +125,396 @@
 //! assert_eq!("hello!", use_trait(&spy));
 //! ```
 //!
+//! ## Conditional returns
+//!
+//! Return values can depend on the captured arguments using [`when()`](Returns::when).
+//! Rules are checked in the order they were added, and any values [`set()`](Returns::set)
+//! are used as the fallback when no rule matches.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait Repository {
+//!     fn get(&self, id: u32) -> Option<String>;
+//! }
+//!
+//! fn use_trait(x: &impl Repository) -> (Option<String>, Option<String>) {
+//!     (x.get(3), x.get(4))
+//! }
+//!
+//! let spy = RepositorySpy::default();
+//! spy.get.returns.when(|id| *id == 3).then_return_once(Some("three".to_string()));
+//! spy.get.returns.set([None]);
+//!
+//! assert_eq!((Some("three".to_string()), None), use_trait(&spy));
+//! assert_eq!([3, 4], spy.get.arguments);
+//! ```
+//!
+//! ## Sequences
+//!
+//! To check the order of calls across several spies, attach their functions to a shared
+//! [`Sequence`] using [`in_sequence()`](SpyFunction::in_sequence). Calls are recorded by their
+//! `Trait::function` name, so functions with the same name on different traits can be told apart.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait Store {
+//!     fn commit(&self);
+//! }
+//!
+//! #[autospy::autospy]
+//! trait Publisher {
+//!     fn send(&self, message: &str);
+//! }
+//!
+//! fn save(store: &impl Store, publisher: &impl Publisher) {
+//!     store.commit();
+//!     publisher.send("saved");
+//! }
+//!
+//! let sequence = autospy::Sequence::default();
+//!
+//! let store = StoreSpy::default();
+//! store.commit.in_sequence(&sequence);
+//! store.commit.returns.set([()]);
+//!
+//! let publisher = PublisherSpy::default();
+//! publisher.send.in_sequence(&sequence);
+//! publisher.send.returns.set([()]);
+//!
+//! save(&store, &publisher);
+//!
+//! sequence.assert_order(["Store::commit", "Publisher::send"]);
+//! ```
+//!
+//! ## Call count expectations
+//!
+//! The number of times a function is called can be checked when the spy is dropped using
+//! [`expect_calls()`](SpyFunction::expect_calls), [`expect_at_least()`](SpyFunction::expect_at_least)
+//! and [`expect_never()`](SpyFunction::expect_never). This also applies when using
+//! [`set_fn()`](Returns::set_fn), which otherwise has no check on drop.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, argument: u32) -> u32;
+//!     fn bar(&self);
+//! }
+//!
+//! fn use_trait(x: &impl MyTrait) -> u32 {
+//!     x.foo(1) + x.foo(2)
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set_fn(|argument| argument * 10);
+//! spy.foo.expect_calls(2);
+//! spy.bar.expect_never();
+//!
+//! assert_eq!(30, use_trait(&spy));
+//! ```
+//!
+//! ## Waiting for calls
+//!
+//! Calls made from other threads can be waited for by blocking the test thread with
+//! [`wait_for()`](Arguments::wait_for) or [`wait_until()`](Arguments::wait_until), which fail the test
+//! if the spy is not called in time. These do not need an async runtime or the [**async**](#features) feature.
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! #[autospy::autospy]
+//! trait MyTrait: Send + Sync + 'static {
+//!     fn foo(&self, argument: u32);
+//! }
+//!
+//! fn use_trait_in_thread(x: impl MyTrait) {
+//!     std::thread::spawn(move || x.foo(10));
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([()]);
+//!
+//! use_trait_in_thread(spy.clone());
+//!
+//! assert_eq!(vec![10], spy.foo.arguments.wait_for(1, Duration::from_secs(5)));
+//! ```
+//!
+//! ## Assertion macros
+//!
+//! [`assert_called!`], [`assert_called_with!`], [`assert_any_call!`] and [`assert_not_called!`]
+//! check the captured arguments of a spy function. When they fail, the panic names the function and
+//! shows each call, with a diff of the expected and actual arguments.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, argument: u32);
+//!     fn bar(&self);
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([(), ()]);
+//!
+//! spy.foo(10);
+//! spy.foo(20);
+//!
+//! autospy::assert_called!(spy.foo, times = 2);
+//! autospy::assert_called_with!(spy.foo, 20);
+//! autospy::assert_called_with!(spy.foo, calls = [10, 20]);
+//! autospy::assert_any_call!(spy.foo, 10);
+//! autospy::assert_not_called!(spy.bar);
+//! ```
+//!
+//! ## Interaction snapshots
+//!
+//! `autospy_render_interactions()` renders the captured arguments of every function of a spy, followed
+//! by the order of all calls when using [`calls`](#call-order). With the [**insta**](#features) feature,
+//! `assert_interactions_snapshot!(spy)` checks this against an [insta](https://docs.rs/insta) snapshot
+//! for review, instead of writing out the expected arguments of each function.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, argument: u32);
+//!     fn bar(&self);
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([(), ()]);
+//!
+//! spy.foo(10);
+//! spy.foo(20);
+//!
+//! assert_eq!("foo:\n  [0] 10\n  [1] 20\nbar: not called", spy.autospy_render_interactions());
+//! ```
+//!
+//! ## Call records
+//!
+//! Every call records the location of the calling code, the thread and a timestamp, available from
+//! [`calls()`](Arguments::calls). This can show which line of production code made an unexpected call.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, argument: u32);
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([()]);
+//!
+//! spy.foo(10);
+//!
+//! for call in spy.foo.arguments.calls() {
+//!     println!("foo called at {} on {:?}", call.location, call.thread.name());
+//! }
+//! ```
+//!
+//! ## Reset and verify
+//!
+//! The spy has `autospy_reset()`, `autospy_checkpoint()` and `autospy_assert_no_more_interactions()`
+//! methods which apply [`reset()`](SpyFunction::reset), [`checkpoint()`](SpyFunction::checkpoint) and
+//! [`assert_no_more_interactions()`](SpyFunction::assert_no_more_interactions) to every function,
+//! allowing one spy to be reused across phases of a test. They are prefixed so they never shadow a
+//! trait function of the same name.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn foo(&self, argument: u32) -> u32;
+//!     fn bar(&self);
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.foo.returns.set([1]);
+//! spy.bar.returns.set([()]);
+//!
+//! assert_eq!(1, spy.foo(10));
+//! spy.bar();
+//!
+//! // all return values have been used so far
+//! spy.autospy_checkpoint();
+//!
+//! assert_eq!([10], spy.foo.arguments);
+//! spy.autospy_reset();
+//! spy.autospy_assert_no_more_interactions();
+//! ```
+//!
+//! ## Panic messages
+//!
+//! When a spy panics because it ran out of return values, or had unused return values, the message
+//! also lists the trait, where the return values were [`set`](Returns::set), any unused return
+//! values and the captured arguments. Types that do not implement [`Debug`](std::fmt::Debug) are
+//! listed by count.
+//!
+//! ```text
+//! function 'foo' had 1 return values set, but was called 2 time(s)
+//!   trait: MyTrait
+//!   return values set at: src/lib.rs:10:21
+//!   captured arguments: [10, 20]
+//! ```
+//!
+//! ## Nice spies
+//!
+//! By default a spy panics when called without a return value set. Adding `nice` to the attribute,
+//! `#[autospy(nice)]`, makes every function return its [`Default`] value instead, using
+//! [`use_default()`](Returns::use_default). A function whose return type does not implement
+//! [`Default`] must declare its own fallback return value with `#[autospy(default_return = "...")]`,
+//! and functions marked `record_default` run their default implementation. Arguments are still captured.
+//!
+//! ```rust
+//! #[autospy::autospy(nice)]
+//! trait Metrics {
+//!     fn count(&self, name: &str) -> u64;
+//!     #[autospy(default_return = "Ok(())")]
+//!     fn flush(&self) -> Result<(), String>;
+//! }
+//!
+//! fn use_trait(x: &impl Metrics) -> (u64, Result<(), String>) {
+//!     (x.count("requests"), x.flush())
+//! }
+//!
+//! let spy = MetricsSpy::default();
+//!
+//! assert_eq!((0, Ok(())), use_trait(&spy));
+//! assert_eq!(["requests"], spy.count.arguments);
+//! ```
+//!
+//! ## Wrapping spies
+//!
+//! Adding `wrapping` to the attribute, `#[autospy(wrapping)]`, generates a `wrapping(inner)`
+//! constructor. Calls to a function with no return value available are forwarded to `inner`, such as a
+//! real implementation or an in-memory fake, while arguments are still captured. Setting return values
+//! overrides just that function. Calls are forwarded before using any fallback return value of a
+//! [nice spy](#nice-spies).
+//!
+//! Only `&self` functions are forwarded, and owned arguments must implement [`Clone`] to be both
+//! captured and forwarded. The trait must be usable as a `dyn` trait object, so async functions need
+//! [`async_trait`](https://docs.rs/async-trait).
+//!
+//! ```rust
+//! #[autospy::autospy(wrapping)]
+//! trait Store: Send + Sync {
+//!     fn get(&self, key: &str) -> Option<u32>;
+//! }
+//!
+//! struct Real;
+//!
+//! impl Store for Real {
+//!     fn get(&self, key: &str) -> Option<u32> {
+//!         key.parse().ok()
+//!     }
+//! }
+//!
+//! let spy = StoreSpy::wrapping(Real);
+//! assert_eq!(Some(1), spy.get("1"));
+//!
+//! spy.get.returns.set([None]);
+//! assert_eq!(None, spy.get("2"));
+//!
+//! assert_eq!(["1", "2"], spy.get.arguments);
+//! ```
+//!
+//! ## Recording and replaying
+//!
+//! Adding `recording` to the attribute, `#[autospy(recording)]`, generates a `recording(inner)`
+//! constructor for a [wrapping spy](#wrapping-spies) which records the arguments and return value of
+//! each call forwarded to `inner`. These are saved as JSON using `save_recording(path)`, so a test can be
+//! run once against a real implementation. Afterwards [`replay_from()`](Returns::replay_from) returns
+//! the recorded values and checks each call is made with the recorded arguments.
+//!
+//! The captured arguments must implement `Serialize` and the return values `Serialize` and
+//! `Deserialize`. Requires the [**serde**](#features) feature, without which the `recording` option
+//! fails to compile.
+//!
+#![cfg_attr(feature = "serde", doc = "```rust")]
+#![cfg_attr(not(feature = "serde"), doc = "```rust,ignore")]
+//! #[autospy::autospy(recording)]
+//! trait Gateway: Send + Sync {
+//!     fn fetch(&self, id: u32) -> Option<String>;
+//! }
+//!
+//! # struct RealGateway;
+//! # impl RealGateway {
+//! #     fn connect() -> Self {
+//! #         Self
+//! #     }
+//! # }
+//! # impl Gateway for RealGateway {
+//! #     fn fetch(&self, id: u32) -> Option<String> {
+//! #         (id == 1).then(|| "alice".to_string())
+//! #     }
+//! # }
+//! let path = std::env::temp_dir().join("autospy_gateway_recording.json");
+//!
+//! // run once against the real implementation
+//! let spy = GatewaySpy::recording(RealGateway::connect());
+//! assert_eq!(Some("alice".to_string()), spy.fetch(1));
+//! spy.save_recording(&path);
+//!
+//! // afterwards replay the recording
+//! let spy = GatewaySpy::default();
+//! spy.fetch.returns.replay_from(&path);
+//! assert_eq!(Some("alice".to_string()), spy.fetch(1));
+//! ```
+//!
+//! ## JSON fixtures
+//!
+//! Return values can be loaded from a JSON array using [`set_from_json()`](Returns::set_from_json) or
+//! [`set_from_file()`](Returns::set_from_file), so large payloads can be kept as fixture files instead of
+//! Rust literals. Captured [`Arguments`] implement `Serialize`, and adding `serialize` to the attribute,
+//! `#[autospy(serialize)]`, implements `Serialize` for the spy as a map of each function name to its
+//! captured arguments. Requires the [**serde**](#features) feature, without which the `serialize`
+//! option fails to compile.
+//!
+#![cfg_attr(feature = "serde", doc = "```rust")]
+#![cfg_attr(not(feature = "serde"), doc = "```rust,ignore")]
+//! #[autospy::autospy(serialize)]
+//! trait Repository {
+//!     fn save(&self, id: u32, name: &str) -> bool;
+//!     fn find(&self, id: u32) -> Option<String>;
+//! }
+//!
+//! let path = std::env::temp_dir().join("autospy_save_returns.json");
+//! std::fs::write(&path, "[true, false]").unwrap();
+//!
+//! let spy = RepositorySpy::default();
+//! spy.save.returns.set_from_file(&path);
+//! spy.find.returns.set_from_json(r#"["alice", null]"#);
+//!
+//! assert!(spy.save(1, "alice"));
+//! assert!(!spy.save(2, "bob"));
+//! assert_eq!(Some("alice".to_string()), spy.find(1));
+//! assert_eq!(None, spy.find(2));
+//! ```
+//!
+//! ## Named arguments
+//!
+//! By default functions with multiple arguments capture them as a tuple. Adding `named_arguments`
+//! to the attribute, `#[autospy(named_arguments)]`, captures them in a generated struct instead,
+//! named `{Trait}Spy{Function}Args`, with a public field per argument. The struct implements
+//! [`Clone`], [`Debug`] and [`PartialEq`] when all its arguments do, and can then be compared
+//! against a tuple of the arguments.
+//!
+//! ```rust
+//! #[autospy::autospy(named_arguments)]
+//! trait Publisher {
+//!     fn send(&self, topic: &str, retries: u8);
+//! }
+//!
+//! fn use_trait(x: &impl Publisher) {
+//!     x.send("orders", 3)
+//! }
+//!
+//! let spy = PublisherSpy::default();
+//! spy.send.returns.set([()]);
+//!
+//! use_trait(&spy);
+//!
+//! let arguments = spy.send.arguments.take();
+//! assert_eq!("orders", arguments[0].topic);
+//! assert_eq!(3, arguments[0].retries);
+//! assert_eq!([("orders".to_string(), 3)], arguments.as_slice());
+//! ```
+//!
 //! ## Ignore arguments
 //!
 //! Arguments can be ignored using `#[autospy(ignore)]` if you do not wish to capture them in the spy.
@@ -220,6 +633,65 @@
 //! assert!(use_trait(spy).is_err());
 //! ```
 //!
+//! ## Out arguments
+//!
+//! Mutable reference arguments marked with `#[autospy(out)]` can be written by the spy. A return
+//! function set with [`set_fn_mut()`](Returns::set_fn_mut) receives the captured arguments followed by the caller's mutable
+//! references. Out arguments are captured as they were passed in, unless they are also marked with
+//! `#[autospy(capture_written)]`, in which case they are captured after the function has written them.
+//! A function can have at most four out arguments.
+//!
+//! ```rust
+//! use std::io::Read;
+//!
+//! #[autospy::autospy(external)]
+//! trait Read {
+//!     fn read(&mut self, #[autospy(out)] buf: &mut [u8]) -> std::io::Result<usize>;
+//! }
+//!
+//! fn use_trait(mut x: impl Read) -> Vec<u8> {
+//!     let mut buf = [0; 8];
+//!     let read = x.read(&mut buf).unwrap();
+//!     buf[..read].to_vec()
+//! }
+//!
+//! let spy = ReadSpy::default();
+//! spy.read.returns.set_fn_mut(|_, buf| {
+//!     buf[..5].copy_from_slice(b"hello");
+//!     Ok(5)
+//! });
+//!
+//! assert_eq!(b"hello".to_vec(), use_trait(spy));
+//! ```
+//!
+//! ## Borrowed return functions
+//!
+//! A function marked with `#[autospy(borrowed)]` can have a return function set with
+//! `set_fn_borrowed()`, which receives the real arguments of the call by reference with their
+//! original lifetimes. This includes ignored arguments, so a spy can call a callback it was given
+//! or return a value borrowed from an argument. The setter comes from a generated trait named after
+//! the spy and function (here `TextSpyFirstWordBorrowed`), which must be in scope to call it.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait Text {
+//!     #[autospy(borrowed)]
+//!     fn first_word<'a>(&self, text: &'a str) -> &'a str;
+//! }
+//!
+//! fn use_trait<'a>(x: &impl Text, text: &'a str) -> &'a str {
+//!     x.first_word(text)
+//! }
+//!
+//! let spy = TextSpy::default();
+//! spy.first_word.returns.set_fn_borrowed(|text| text.split(' ').next().unwrap());
+//!
+//! let text = String::from("hello world");
+//!
+//! assert_eq!("hello", use_trait(&spy, &text));
+//! assert_eq!(["hello world"], spy.first_word.arguments);
+//! ```
+//!
 //! ## Returns attribute
 //!
 //! Trait functions that return generics can have the return type specified using the
@@ -351,6 +823,51 @@
 //! })
 //! ```
 //!
+//! To wait for particular calls instead, use [`recv_n()`](Arguments::recv_n), [`recv_until()`](Arguments::recv_until)
+//! or [`next_call()`](Arguments::next_call), which leave any other captured arguments in place. Each has a timeout
+//! variant, such as [`next_call_timeout()`](Arguments::next_call_timeout), which fails the test instead of waiting forever.
+//! Captured arguments can also be consumed as a [`stream()`](Arguments::stream).
+//!
+//! To stand in for a slow service, async functions can return after a delay with
+//! [`set_with_delay()`](Returns::set_with_delay) or [`delay_all()`](Returns::delay_all).
+//!
+//! Async functions can also be [`controlled()`](Returns::controlled), leaving each call pending until the test responds to it.
+//! This makes it possible to test timeouts, cancellation and concurrent requests deterministically.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! #[async_trait::async_trait]
+//! trait MyTrait: Send + Sync + 'static {
+//!     async fn foo(&self, argument: u8) -> u8;
+//! }
+//!
+//! tokio::runtime::Runtime::new().unwrap().block_on(async {
+//!     let spy = MyTraitSpy::default();
+//!     let controller = spy.foo.returns.controlled();
+//!
+//!     let first = tokio::task::spawn({
+//!         let spy = spy.clone();
+//!         async move { spy.foo(1).await }
+//!     });
+//!     let second = tokio::task::spawn({
+//!         let spy = spy.clone();
+//!         async move { spy.foo(2).await }
+//!     });
+//!
+//!     let call = controller.next_call().await;
+//!     let other_call = controller.next_call().await;
+//!
+//!     // respond to the calls in any order
+//!     let other_value = other_call.arguments() * 10;
+//!     other_call.respond(other_value);
+//!     let value = call.arguments() * 10;
+//!     call.respond(value);
+//!
+//!     assert_eq!(10, first.await.unwrap());
+//!     assert_eq!(20, second.await.unwrap());
+//! })
+//! ```
+//!
 //! ## Into attribute
 //!
 //! If you wish to capture an argument as a different type, and it implements [`From`] you can
@@ -443,6 +960,28 @@
 //! assert_eq!(120, use_trait(MyTraitSpy::default()));
 //! ```
 //!
+//! To record calls to a default implementation, use `#[autospy(record_default)]` instead. The
+//! arguments are captured before the default implementation is run, which may in turn call other spied
+//! functions. Owned arguments must implement [`Clone`] and return values are not used.
+//!
+//! ```rust
+//! #[autospy::autospy]
+//! trait MyTrait {
+//!     fn get(&self, key: &str) -> Option<u64>;
+//!     #[autospy(record_default)]
+//!     fn get_or(&self, key: &str, fallback: u64) -> u64 {
+//!         self.get(key).unwrap_or(fallback)
+//!     }
+//! }
+//!
+//! let spy = MyTraitSpy::default();
+//! spy.get.returns.set([None]);
+//!
+//! assert_eq!(5, spy.get_or("key", 5));
+//! assert_eq!([("key".to_string(), 5)], spy.get_or.arguments);
+//! assert_eq!(["key"], spy.get.arguments);
+//! ```
+//!
 //! ## Supertraits
 //!
 //! Supertraits are supported through the [`supertrait!`](supertrait) macro by putting the supertrait
@@ -476,6 +1015,45 @@
 //! assert_eq!(0, result.1.unwrap())
 //! ```
 //!
+//! ## Call order
+//!
+//! Adding `calls` to the attribute, `#[autospy(calls)]`, records every call made to the spy in
+//! a single ordered log, accessed through [`autospy_calls()`](Calls). Each call is a variant of a
+//! generated `{Trait}SpyCall` enum, named after the function and holding a copy of its captured
+//! arguments, so they must implement [`Clone`]. The enum implements [`Debug`] and [`PartialEq`]
+//! when all the captured arguments do.
+//!
+//! ```rust
+//! #[autospy::autospy(calls)]
+//! trait Connection {
+//!     fn open(&self);
+//!     fn write(&self, bytes: &[u8]) -> usize;
+//!     fn close(&self);
+//! }
+//!
+//! fn send(connection: &impl Connection, bytes: &[u8]) {
+//!     connection.open();
+//!     connection.write(bytes);
+//!     connection.close();
+//! }
+//!
+//! let spy = ConnectionSpy::default();
+//! spy.open.returns.set([()]);
+//! spy.write.returns.set([2]);
+//! spy.close.returns.set([()]);
+//!
+//! send(&spy, &[1, 2]);
+//!
+//! assert_eq!(
+//!     [
+//!         ConnectionSpyCall::Open,
+//!         ConnectionSpyCall::Write(vec![1, 2]),
+//!         ConnectionSpyCall::Close,
+//!     ],
+//!     spy.autospy_calls()
+//! );
+//! ```
+//!
 
 //! # Examples
 //!
@@ -486,10 +1064,46 @@
 //! - **test** - makes the generated spy object and trait impl `#[cfg(test)]` - enabled by default.
 //! - **async** - enables additional async support features on the spy, if you are not using async
 //!   traits you can safely disable this - enabled by default.
+//! - **tokio** - uses `tokio::time` for [delayed](Returns::delay_all) async returns made inside a tokio
+//!   runtime, so they respect paused time in tokio tests.
+//! - **insta** - enables [`assert_interactions_snapshot!`] for [interaction snapshots](#interaction-snapshots).
+//! - **serde** - enables [recording and replaying](#recording-and-replaying) spy interactions and
+//!   [JSON fixtures](#json-fixtures).
 
+mod assertions;
+mod call_record;
+mod calls;
+#[cfg(feature = "async")]
+mod controlled;
+#[cfg(feature = "async")]
+mod delay;
+mod diagnostics;
+mod in_flight;
 mod macros;
+mod notifier;
+#[cfg(feature = "serde")]
+mod recording;
+mod sequence;
 mod spy_function;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "async")]
+mod timer;
 
+/// Where, when and on which thread a spy function was called.
+pub use call_record::CallRecord;
+/// The ordered calls made to a spy.
+pub use calls::Calls;
+/// Controls the responses of an async spy function.
+#[cfg(feature = "async")]
+pub use controlled::Controller;
+/// A call to a controlled async spy function waiting for a response.
+#[cfg(feature = "async")]
+pub use controlled::PendingCall;
+/// The outcome of a call to an async spy function.
+pub use in_flight::CallOutcome;
+/// The order spy functions are called in, across spies.
+pub use sequence::Sequence;
 /// The captured arguments of a spy function.
 #[allow(unused_imports)]
 pub use spy_function::Arguments;
@@ -498,6 +1112,28 @@ pub use spy_function::Arguments;
 pub use spy_function::Returns;
 /// Captures arguments and holds return values.
 pub use spy_function::SpyFunction;
+/// A conditional return rule of a spy function.
+pub use spy_function::When;
+
+#[doc(hidden)]
+pub use assertions::{
+    assert_any_call as __assert_any_call, assert_called as __assert_called,
+    assert_called_with as __assert_called_with, assert_calls as __assert_calls,
+    assert_not_called as __assert_not_called,
+};
+#[doc(hidden)]
+pub use diagnostics::{DebugProbe, FormatWithDebug, FormatWithoutDebug};
+#[cfg(feature = "insta")]
+#[doc(hidden)]
+pub use insta as __insta;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use recording::save_recording as __save_recording;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
+#[doc(hidden)]
+pub use spy_function::NiceReturn;
 
 /// Automatically generate spy objects for traits.
 ///
@@ -33,3 +33,268 @@
 macro_rules! supertrait {
     ($($tt:tt)*) => {};
 }
+
+/// Asserts a spy function has been called, optionally an exact number of `times`.
+///
+/// On failure the panic names the spy function and lists each captured call.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self, argument: u8);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([(), ()]);
+///
+/// spy.foo(1);
+/// spy.foo(2);
+///
+/// autospy::assert_called!(spy.foo);
+/// autospy::assert_called!(spy.foo, times = 2);
+/// ```
+#[macro_export]
+macro_rules! assert_called {
+    ($spy_function:expr $(,)?) => {
+        $crate::__assert_called(&$spy_function.arguments, ::core::option::Option::None)
+    };
+    ($spy_function:expr, times = $times:expr $(,)?) => {
+        $crate::__assert_called(
+            &$spy_function.arguments,
+            ::core::option::Option::Some($times),
+        )
+    };
+}
+
+/// Asserts the last call to a spy function was with the expected arguments, or with `calls = `
+/// that every captured call matches, in order.
+///
+/// The expected calls can be an array, [`Vec`] or slice, compared in the same way as
+/// [`Arguments`](crate::Arguments). On failure the panic names the spy function and shows a
+/// per-call diff of the expected and actual arguments.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self, id: u8, name: &str);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([(), ()]);
+///
+/// spy.foo(1, "a");
+/// spy.foo(2, "b");
+///
+/// autospy::assert_called_with!(spy.foo, (2, "b".to_string()));
+/// autospy::assert_called_with!(spy.foo, calls = [(1, "a".to_string()), (2, "b".to_string())]);
+/// ```
+///
+/// ```should_panic
+/// # #[autospy::autospy]
+/// # trait MyTrait {
+/// #     fn foo(&self, id: u8);
+/// # }
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([(), ()]);
+///
+/// spy.foo(1);
+/// spy.foo(3);
+///
+/// // function 'foo' was not called with the expected arguments (- expected, + actual):
+/// //    [0] 1
+/// //   -[1] 2
+/// //   +[1] 3
+/// autospy::assert_called_with!(spy.foo, calls = [1, 2]);
+/// ```
+#[macro_export]
+macro_rules! assert_called_with {
+    ($spy_function:expr, calls = $calls:expr $(,)?) => {
+        $crate::__assert_calls(&$spy_function.arguments, $calls)
+    };
+    ($spy_function:expr, $arguments:expr $(,)?) => {
+        $crate::__assert_called_with(&$spy_function.arguments, &$arguments)
+    };
+}
+
+/// Asserts any call to a spy function was with the expected arguments.
+///
+/// On failure the panic names the spy function and lists each captured call.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self, argument: &str);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([(), ()]);
+///
+/// spy.foo("a");
+/// spy.foo("b");
+///
+/// autospy::assert_any_call!(spy.foo, "a");
+/// ```
+#[macro_export]
+macro_rules! assert_any_call {
+    ($spy_function:expr, $arguments:expr $(,)?) => {
+        $crate::__assert_any_call(&$spy_function.arguments, &$arguments)
+    };
+}
+
+/// Asserts a spy function has not been called.
+///
+/// On failure the panic names the spy function and lists each captured call.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+///
+/// autospy::assert_not_called!(spy.foo);
+/// ```
+#[macro_export]
+macro_rules! assert_not_called {
+    ($spy_function:expr $(,)?) => {
+        $crate::__assert_not_called(&$spy_function.arguments)
+    };
+}
+
+/// Asserts the [interactions](crate#interaction-snapshots) of a spy match an
+/// [insta](https://docs.rs/insta) snapshot, optionally with a snapshot name.
+///
+/// Each function is listed with the captured arguments of its calls, followed by the order of all
+/// calls when the spy is generated with `calls`. Requires the **insta** feature.
+///
+/// # Examples
+/// ```rust,ignore
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self, argument: u8);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([()]);
+///
+/// spy.foo(1);
+///
+/// autospy::assert_interactions_snapshot!(spy);
+/// autospy::assert_interactions_snapshot!("foo_called_once", spy);
+/// ```
+#[cfg(feature = "insta")]
+#[macro_export]
+macro_rules! assert_interactions_snapshot {
+    ($spy:expr $(,)?) => {
+        $crate::__insta::assert_snapshot!($spy.autospy_render_interactions())
+    };
+    ($name:expr, $spy:expr $(,)?) => {
+        $crate::__insta::assert_snapshot!($name, $spy.autospy_render_interactions())
+    };
+}
+
+/// Expands to the items of a spy option that needs the **serde** feature, or to an error naming the
+/// option when the feature is disabled.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __requires_serde {
+    ($option:literal, $($items:tt)*) => {
+        $($items)*
+    };
+}
+
+/// Expands to the items of a spy option that needs the **serde** feature, or to an error naming the
+/// option when the feature is disabled.
+///
+/// ```compile_fail
+/// #[autospy::autospy(recording)]
+/// trait Gateway: Send + Sync {
+///     fn fetch(&self, id: u32) -> Option<String>;
+/// }
+/// ```
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __requires_serde {
+    ($option:literal, $($items:tt)*) => {
+        compile_error!(concat!(
+            "the `",
+            $option,
+            "` option requires autospy's `serde` feature"
+        ));
+    };
+}
+
+/// Implements [`PartialEq`] in both directions between a recorded list and arrays, slices and
+/// vectors of anything it can be compared with.
+macro_rules! impl_slice_partial_eq {
+    ($recorded:ident) => {
+        impl<A, B: PartialEq<A>> PartialEq<[B]> for $recorded<A> {
+            fn eq(&self, other: &[B]) -> bool {
+                other == self.get().as_slice()
+            }
+        }
+
+        impl<A: PartialEq<B>, B> PartialEq<$recorded<A>> for [B] {
+            fn eq(&self, other: &$recorded<A>) -> bool {
+                other.get().as_slice() == self
+            }
+        }
+
+        impl<A, B: PartialEq<A>, const N: usize> PartialEq<[B; N]> for $recorded<A> {
+            fn eq(&self, other: &[B; N]) -> bool {
+                other == self.get().as_slice()
+            }
+        }
+
+        impl<A: PartialEq<B>, B, const N: usize> PartialEq<$recorded<A>> for [B; N] {
+            fn eq(&self, other: &$recorded<A>) -> bool {
+                other.get().as_slice() == self
+            }
+        }
+
+        impl<A, B: PartialEq<A>, const N: usize> PartialEq<&[B; N]> for $recorded<A> {
+            fn eq(&self, other: &&[B; N]) -> bool {
+                *other == self.get().as_slice()
+            }
+        }
+
+        impl<A: PartialEq<B>, B, const N: usize> PartialEq<$recorded<A>> for &[B; N] {
+            fn eq(&self, other: &$recorded<A>) -> bool {
+                other.get().as_slice() == *self
+            }
+        }
+
+        impl<A, B: PartialEq<A>> PartialEq<&[B]> for $recorded<A> {
+            fn eq(&self, other: &&[B]) -> bool {
+                *other == self.get().as_slice()
+            }
+        }
+
+        impl<A: PartialEq<B>, B> PartialEq<$recorded<A>> for &[B] {
+            fn eq(&self, other: &$recorded<A>) -> bool {
+                other.get().as_slice() == *self
+            }
+        }
+
+        impl<A: PartialEq<B>, B> PartialEq<Vec<B>> for $recorded<A> {
+            fn eq(&self, other: &Vec<B>) -> bool {
+                *self.get() == *other
+            }
+        }
+
+        impl<A, B: PartialEq<A>> PartialEq<$recorded<A>> for Vec<B> {
+            fn eq(&self, other: &$recorded<A>) -> bool {
+                *self == *other.get()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_slice_partial_eq;
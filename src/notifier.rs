@@ -0,0 +1,43 @@
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::task::Waker;
+use std::time::Duration;
+
+/// Wakes threads and tasks waiting on a value guarded by a [`Mutex`], without queueing a
+/// notification for every change.
+///
+/// Waiters must check the guarded value and then wait while still holding its lock, so no
+/// change can be missed in between.
+#[derive(Default)]
+pub struct Notifier {
+    condvar: Condvar,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Notifier {
+    pub fn notify(&self) {
+        self.condvar.notify_all();
+        let wakers = std::mem::take(&mut *self.wakers.lock().expect("mutex poisoned"));
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout: Duration,
+    ) -> MutexGuard<'a, T> {
+        self.condvar
+            .wait_timeout(guard, timeout)
+            .expect("mutex poisoned")
+            .0
+    }
+
+    #[cfg(feature = "async")]
+    pub fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().expect("mutex poisoned");
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
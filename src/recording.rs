@@ -0,0 +1,151 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+pub struct Recorder<A, R> {
+    serialize_arguments: fn(&A) -> Value,
+    serialize_return: fn(&R) -> Value,
+    interactions: Mutex<Vec<Value>>,
+}
+
+impl<A: Serialize, R: Serialize> Default for Recorder<A, R> {
+    fn default() -> Self {
+        Self {
+            serialize_arguments: to_value,
+            serialize_return: to_value,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<A, R> Recorder<A, R> {
+    pub fn arguments(&self, arguments: &A) -> Value {
+        (self.serialize_arguments)(arguments)
+    }
+
+    pub fn record(&self, arguments: Value, return_value: &R) {
+        let mut interaction = Map::new();
+        interaction.insert("arguments".to_string(), arguments);
+        interaction.insert("returns".to_string(), (self.serialize_return)(return_value));
+        self.interactions
+            .lock()
+            .expect("mutex poisoned")
+            .push(Value::Object(interaction));
+    }
+
+    pub fn interactions(&self) -> Vec<Value> {
+        self.interactions.lock().expect("mutex poisoned").clone()
+    }
+}
+
+pub struct Replay<A, R> {
+    name: &'static str,
+    path: String,
+    serialize_arguments: fn(&A) -> Value,
+    interactions: VecDeque<(Value, R)>,
+    call_count: usize,
+}
+
+impl<A, R> Replay<A, R> {
+    #[track_caller]
+    pub fn load(name: &'static str, path: &Path) -> Self
+    where
+        A: Serialize,
+        R: DeserializeOwned,
+    {
+        let display_path = path.display().to_string();
+        let recording = std::fs::read_to_string(path)
+            .unwrap_or_else(|error| panic!("could not read recording '{display_path}': {error}"));
+        let mut recording: Map<String, Value> = serde_json::from_str(&recording)
+            .unwrap_or_else(|error| panic!("could not parse recording '{display_path}': {error}"));
+        let Some(Value::Array(interactions)) = recording.remove(name) else {
+            panic!("recording '{display_path}' has no interactions for function '{name}'")
+        };
+
+        let interactions = interactions
+            .into_iter()
+            .enumerate()
+            .map(|(index, interaction)| {
+                let Value::Object(mut interaction) = interaction else {
+                    panic!("recording '{display_path}' has an invalid interaction [{index}] for function '{name}'")
+                };
+                let arguments = interaction.remove("arguments").unwrap_or_default();
+                let return_value =
+                    serde_json::from_value(interaction.remove("returns").unwrap_or_default())
+                        .unwrap_or_else(|error| {
+                            panic!("could not parse return value [{index}] of function '{name}' in recording '{display_path}': {error}")
+                        });
+                (arguments, return_value)
+            })
+            .collect();
+
+        Self {
+            name,
+            path: display_path,
+            serialize_arguments: to_value,
+            interactions,
+            call_count: 0,
+        }
+    }
+
+    pub fn next(&mut self, arguments: &A) -> Option<R> {
+        let (recorded_arguments, return_value) = self.interactions.pop_front()?;
+        let index = self.call_count;
+        self.call_count += 1;
+
+        let actual_arguments = (self.serialize_arguments)(arguments);
+        assert!(
+            recorded_arguments == actual_arguments,
+            "function '{}' call [{index}] did not match recording '{}' (- recorded, + actual):\n  -[{index}] {}\n  +[{index}] {}",
+            self.name,
+            self.path,
+            recorded_arguments,
+            actual_arguments
+        );
+
+        Some(return_value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.interactions.len()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &R> {
+        self.interactions
+            .iter()
+            .map(|(_, return_value)| return_value)
+    }
+}
+
+/// Saves the interactions of each recorded spy function to `path`, keyed by function name.
+///
+/// # Panics
+/// Panics if the recording could not be written.
+#[track_caller]
+pub fn save_recording(
+    path: impl AsRef<Path>,
+    functions: impl IntoIterator<Item = (&'static str, Vec<Value>)>,
+) {
+    let path = path.as_ref();
+    let recording: Map<String, Value> = functions
+        .into_iter()
+        .map(|(name, interactions)| (name.to_string(), Value::Array(interactions)))
+        .collect();
+    let recording =
+        serde_json::to_string_pretty(&recording).expect("recording is always valid json");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap_or_else(|error| {
+            panic!("could not create directory '{}': {error}", parent.display())
+        });
+    }
+    std::fs::write(path, recording)
+        .unwrap_or_else(|error| panic!("could not write recording '{}': {error}", path.display()));
+}
+
+fn to_value<T: Serialize + ?Sized>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|error| panic!("could not serialize value: {error}"))
+}
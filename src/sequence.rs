@@ -0,0 +1,103 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+/// Records the order spy functions are called in, across any number of spies.
+///
+/// Spy functions are attached with [`in_sequence()`](crate::SpyFunction::in_sequence),
+/// after which every call is recorded by its `Trait::function` name.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait Store {
+///     fn commit(&self);
+/// }
+///
+/// #[autospy::autospy]
+/// trait Publisher {
+///     fn send(&self, message: &str);
+/// }
+///
+/// fn save(store: &impl Store, publisher: &impl Publisher) {
+///     store.commit();
+///     publisher.send("saved");
+/// }
+///
+/// let sequence = autospy::Sequence::default();
+///
+/// let store = StoreSpy::default();
+/// store.commit.in_sequence(&sequence);
+/// store.commit.returns.set([()]);
+///
+/// let publisher = PublisherSpy::default();
+/// publisher.send.in_sequence(&sequence);
+/// publisher.send.returns.set([()]);
+///
+/// save(&store, &publisher);
+///
+/// sequence.assert_order(["Store::commit", "Publisher::send"]);
+/// ```
+#[derive(Clone, Default)]
+pub struct Sequence {
+    interactions: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Debug for Sequence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.interactions.lock() {
+            Ok(interactions) => interactions.fmt(f),
+            Err(_) => write!(f, "mutex poisoned"),
+        }
+    }
+}
+
+impl Sequence {
+    pub(crate) fn record(&self, name: &'static str) {
+        self.interactions.lock().expect("mutex poisoned").push(name);
+    }
+
+    /// Gets the `Trait::function` names of the functions called, in the order they were called.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self);
+    ///     fn bar(&self);
+    /// }
+    ///
+    /// let sequence = autospy::Sequence::default();
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.in_sequence(&sequence);
+    /// spy.bar.in_sequence(&sequence);
+    /// spy.foo.returns.set([()]);
+    /// spy.bar.returns.set([()]);
+    ///
+    /// spy.bar();
+    /// spy.foo();
+    ///
+    /// assert_eq!(vec!["MyTrait::bar", "MyTrait::foo"], sequence.interactions());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn interactions(&self) -> Vec<&'static str> {
+        self.interactions.lock().expect("mutex poisoned").clone()
+    }
+
+    /// Asserts the functions were called in exactly the expected order, given by their
+    /// `Trait::function` names.
+    ///
+    /// # Panics
+    /// Panics showing the expected and actual order if they differ.
+    #[track_caller]
+    pub fn assert_order<'a>(&self, expected: impl IntoIterator<Item = &'a str>) {
+        let expected: Vec<&str> = expected.into_iter().collect();
+        let actual = self.interactions();
+
+        assert!(
+            expected == actual,
+            "sequence did not match the expected order\nexpected: {expected:?}\n  actual: {actual:?}"
+        );
+    }
+}
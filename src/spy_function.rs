@@ -1,410 +1,1409 @@
+use crate::Sequence;
+use crate::call_record::CallRecord;
+#[cfg(feature = "async")]
+use crate::controlled::{Controller, PendingCall};
+#[cfg(feature = "async")]
+use crate::delay::{self, Delays};
+use crate::diagnostics::Diagnostics;
+use crate::in_flight::{CallOutcome, InFlightCall, InFlightCalls};
+use crate::macros::impl_slice_partial_eq;
+use crate::notifier::Notifier;
+#[cfg(feature = "serde")]
+use crate::recording::{Recorder, Replay};
+#[cfg(feature = "async")]
+use crate::stream::ArgumentsStream;
 use std::fmt::{Debug, Formatter};
-use std::sync::MutexGuard;
+use std::panic::Location;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{MutexGuard, OnceLock};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use std::{
     collections::VecDeque,
     sync::{Arc, Mutex},
 };
 
-pub struct SpyFunction<A, R> {
+#[cfg(feature = "async")]
+struct ControllerSender<A, R> {
+    sender: async_channel::Sender<PendingCall<A, R>>,
+    clone: fn(&A) -> A,
+}
+
+#[cfg(feature = "async")]
+impl<A, R> Clone for ControllerSender<A, R> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            clone: self.clone,
+        }
+    }
+}
+
+pub struct SpyFunction<A, R, H: ?Sized = ()> {
     /// The captured arguments the function was called with.
     pub arguments: Arguments<A>,
     /// The return values of the function.
-    pub returns: Returns<A, R>,
+    pub returns: Returns<A, R, H>,
     name: &'static str,
+    sequences: Arc<Mutex<Vec<Sequence>>>,
+    call_count: Arc<AtomicUsize>,
+    expected_calls: Arc<Mutex<Option<ExpectedCalls>>>,
+    in_flight_calls: Arc<Mutex<InFlightCalls>>,
+    diagnostics: Arc<OnceLock<Diagnostics<A, R>>>,
+    #[cfg(feature = "serde")]
+    recorder: Arc<OnceLock<Recorder<A, R>>>,
 }
 
-impl<A, R> Clone for SpyFunction<A, R> {
+impl<A, R, H: ?Sized> Clone for SpyFunction<A, R, H> {
     fn clone(&self) -> Self {
         Self {
             arguments: self.arguments.clone(),
             returns: self.returns.clone(),
             name: self.name,
+            sequences: self.sequences.clone(),
+            call_count: self.call_count.clone(),
+            expected_calls: self.expected_calls.clone(),
+            in_flight_calls: self.in_flight_calls.clone(),
+            diagnostics: self.diagnostics.clone(),
+            #[cfg(feature = "serde")]
+            recorder: self.recorder.clone(),
         }
     }
 }
 
-impl<A, R> From<&'static str> for SpyFunction<A, R> {
+impl<A, R, H: ?Sized> From<&'static str> for SpyFunction<A, R, H> {
     fn from(name: &'static str) -> Self {
         Self {
-            arguments: Arguments::default(),
-            returns: Returns::default(),
+            arguments: Arguments::from(name),
+            returns: Returns {
+                name,
+                ..Returns::default()
+            },
             name,
+            sequences: Arc::new(Mutex::new(Vec::new())),
+            call_count: Arc::new(AtomicUsize::new(0)),
+            expected_calls: Arc::new(Mutex::new(None)),
+            in_flight_calls: Arc::new(Mutex::new(InFlightCalls::default())),
+            diagnostics: Arc::new(OnceLock::new()),
+            #[cfg(feature = "serde")]
+            recorder: Arc::new(OnceLock::new()),
         }
     }
 }
 
-impl<A, R> Drop for SpyFunction<A, R> {
+impl<A, R, H: ?Sized> Drop for SpyFunction<A, R, H> {
     fn drop(&mut self) {
-        if !std::thread::panicking()
-            && self.returns.is_last_reference()
-            && self.returns.queue_len() != 0
+        if std::thread::panicking() || !self.returns.is_last_reference() {
+            return;
+        }
+
+        let unused_count = self.returns.queue_len();
+        assert!(
+            unused_count == 0,
+            "function '{}' had {} unused return values when dropped{}",
+            self.name,
+            unused_count,
+            self.details()
+        );
+
+        let call_count = self.call_count.load(Ordering::Relaxed);
+        if let Some(expected_calls) = *self.expected_calls.lock().expect("mutex poisoned")
+            && !expected_calls.is_met_by(call_count)
         {
             panic!(
-                "function '{}' had {} unused return values when dropped",
-                self.name,
-                self.returns.queue_len()
+                "function '{}' was expected to be called {}, but was called {} time(s)",
+                self.name, expected_calls, call_count
             )
         }
     }
 }
 
-impl<A, R> SpyFunction<A, R> {
+impl<A, R, H: ?Sized> SpyFunction<A, R, H> {
     /// Captures the arguments into [`arguments`](Self::arguments) and tries to return the next value from [`returns`](Self::returns).
     /// # Panics
     /// The spy will panic if not enough return values have been set for the number of times the function is called.
     #[track_caller]
     pub fn spy(&self, arguments: A) -> R {
-        let return_value = self.returns.next(&arguments);
+        self.spy_at(arguments, Location::caller())
+    }
 
-        self.arguments.push(arguments);
+    /// Captures the arguments and returns the next value from [`returns`](Self::returns) after any
+    /// [`delay`](Returns::delay_all), or waits for a response if the returns are [`controlled`](Returns::controlled).
+    /// # Panics
+    /// The spy will panic if not enough return values have been set for the number of times the function is called,
+    /// or if a controlled call is dropped without a response.
+    #[track_caller]
+    pub fn spy_async(&self, arguments: A) -> impl Future<Output = R> {
+        let location = Location::caller();
+        async move {
+            let in_flight_call = InFlightCall::start(&self.in_flight_calls);
+            let return_value = self
+                .spy_async_return(
+                    arguments,
+                    location,
+                    Fallback::Use,
+                    || -> std::future::Ready<R> { self.called_too_many_times() },
+                )
+                .await;
+            in_flight_call.complete();
+            return_value
+        }
+    }
 
-        return_value.unwrap_or_else(|_| {
-            let set_count = self.returns.set_count.load(Ordering::Relaxed);
-            panic!(
-                "function '{}' had {} return values set, but was called {} time(s)",
-                self.name,
-                set_count,
-                set_count.saturating_add(1)
+    /// Captures the arguments and returns the next set value from [`returns`](Self::returns), or the
+    /// result of `forward` if there is none, used by spies created with `wrapping`. Any fallback
+    /// return value is not used, as the call is forwarded instead.
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn spy_or_else(&self, arguments: A, forward: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "serde")]
+        let recorded_arguments = self.recorded_arguments(&arguments);
+        self.try_spy_at(arguments, Location::caller(), Fallback::Skip)
+            .map_or_else(
+                |_| {
+                    let return_value = forward();
+                    #[cfg(feature = "serde")]
+                    self.record_forwarded(recorded_arguments, &return_value);
+                    return_value
+                },
+                |next| next.value,
             )
-        })
     }
-}
-
-///
-/// Arguments implements [`PartialEq`] for `[A]`, `&[A]` and `Vec<A>`.
-///
-/// # Examples
-/// ```rust
-/// #[autospy::autospy]
-/// trait MyTrait {
-///     fn foo(&self, bar: u8);
-/// }
-///
-/// fn use_trait(trait_object: &impl MyTrait) {
-///     trait_object.foo(10)
-/// }
-///
-/// let spy = MyTraitSpy::default();
-/// spy.foo.returns.set([()]);
-///
-/// use_trait(&spy);
-///
-/// // all valid PartialEq implementations
-/// assert_eq!([10], spy.foo.arguments);
-/// assert_eq!([10].as_slice(), spy.foo.arguments);
-/// assert_eq!(vec![10], spy.foo.arguments);
-/// ```
-pub struct Arguments<A> {
-    captured: Arc<Mutex<Vec<A>>>,
-    #[cfg(feature = "async")]
-    sender: async_channel::Sender<()>,
-    #[cfg(feature = "async")]
-    receiver: async_channel::Receiver<()>,
-}
 
-impl<A: Debug> Debug for Arguments<A> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self.captured.lock() {
-            Ok(captured) => captured.fmt(f),
-            Err(_) => write!(f, "mutex poisoned"),
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn spy_async_or_else<F: Future<Output = R>>(
+        &self,
+        arguments: A,
+        forward: impl FnOnce() -> F,
+    ) -> impl Future<Output = R> {
+        let location = Location::caller();
+        async move {
+            let in_flight_call = InFlightCall::start(&self.in_flight_calls);
+            #[cfg(feature = "serde")]
+            let recorded_arguments = self.recorded_arguments(&arguments);
+            let return_value = self
+                .spy_async_return(arguments, location, Fallback::Skip, || async {
+                    let return_value = forward().await;
+                    #[cfg(feature = "serde")]
+                    self.record_forwarded(recorded_arguments, &return_value);
+                    return_value
+                })
+                .await;
+            in_flight_call.complete();
+            return_value
         }
     }
-}
 
-impl<A> Clone for Arguments<A> {
-    fn clone(&self) -> Self {
-        Self {
-            captured: self.captured.clone(),
-            #[cfg(feature = "async")]
-            sender: self.sender.clone(),
-            #[cfg(feature = "async")]
-            receiver: self.receiver.clone(),
-        }
+    /// Records the arguments and return value of calls forwarded by spies created with `recording`.
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    pub fn record_forwarded_calls(&self)
+    where
+        A: serde::Serialize,
+        R: serde::Serialize,
+    {
+        let _ = self.recorder.set(Recorder::default());
     }
-}
 
-impl<A> Default for Arguments<A> {
-    fn default() -> Self {
-        #[cfg(feature = "async")]
-        let (sender, receiver) = async_channel::unbounded();
-        Self {
-            captured: Arc::new(Mutex::new(Vec::new())),
-            #[cfg(feature = "async")]
-            sender,
-            #[cfg(feature = "async")]
-            receiver,
-        }
+    #[cfg(feature = "serde")]
+    #[doc(hidden)]
+    pub fn recorded_interactions(&self) -> Vec<serde_json::Value> {
+        self.recorder
+            .get()
+            .map(Recorder::interactions)
+            .unwrap_or_default()
     }
-}
 
-impl<A, B: PartialEq<A>> PartialEq<[B]> for Arguments<A> {
-    fn eq(&self, other: &[B]) -> bool {
-        other == self.get().as_slice()
+    #[cfg(feature = "serde")]
+    fn recorded_arguments(&self, arguments: &A) -> Option<serde_json::Value> {
+        self.recorder
+            .get()
+            .map(|recorder| recorder.arguments(arguments))
     }
-}
 
-impl<A: PartialEq<B>, B> PartialEq<Arguments<A>> for [B] {
-    fn eq(&self, other: &Arguments<A>) -> bool {
-        other.get().as_slice() == self
+    #[cfg(feature = "serde")]
+    fn record_forwarded(&self, arguments: Option<serde_json::Value>, return_value: &R) {
+        if let (Some(recorder), Some(arguments)) = (self.recorder.get(), arguments) {
+            recorder.record(arguments, return_value);
+        }
     }
-}
 
-impl<A, B: PartialEq<A>, const N: usize> PartialEq<[B; N]> for Arguments<A> {
-    fn eq(&self, other: &[B; N]) -> bool {
-        other == self.get().as_slice()
+    fn spy_at(&self, arguments: A, location: &'static Location<'static>) -> R {
+        self.try_spy_at(arguments, location, Fallback::Use)
+            .map_or_else(|_| self.called_too_many_times(), |next| next.value)
     }
-}
 
-impl<A: PartialEq<B>, B, const N: usize> PartialEq<Arguments<A>> for [B; N] {
-    fn eq(&self, other: &Arguments<A>) -> bool {
-        other.get().as_slice() == self
+    fn try_spy_at(
+        &self,
+        arguments: A,
+        location: &'static Location<'static>,
+        fallback: Fallback,
+    ) -> Result<NextReturn<R>, CalledTooManyTimesError> {
+        let next = self.returns.next(&arguments, fallback);
+
+        self.capture_at(arguments, location);
+
+        next
     }
-}
 
-impl<A, B: PartialEq<A>, const N: usize> PartialEq<&[B; N]> for Arguments<A> {
-    fn eq(&self, other: &&[B; N]) -> bool {
-        *other == self.get().as_slice()
+    fn called_too_many_times(&self) -> ! {
+        let set_count = self.returns.set_count.load(Ordering::Relaxed);
+        let rule_count = self.returns.rule_count.load(Ordering::Relaxed);
+        let rule_values = if rule_count == 0 {
+            String::new()
+        } else {
+            format!(" and {rule_count} conditional return values")
+        };
+        panic!(
+            "function '{}' had {} return values set{}, but was called {} time(s){}",
+            self.name,
+            set_count,
+            rule_values,
+            self.call_count.load(Ordering::Relaxed),
+            self.details()
+        )
     }
-}
 
-impl<A: PartialEq<B>, B, const N: usize> PartialEq<Arguments<A>> for &[B; N] {
-    fn eq(&self, other: &Arguments<A>) -> bool {
-        other.get().as_slice() == *self
+    #[allow(clippy::future_not_send)]
+    async fn spy_async_return<F: Future<Output = R>>(
+        &self,
+        arguments: A,
+        location: &'static Location<'static>,
+        fallback: Fallback,
+        or_else: impl FnOnce() -> F,
+    ) -> R {
+        #[cfg(feature = "async")]
+        if let Some(controller) = self.returns.controller() {
+            return self.spy_controlled(&controller, arguments, location).await;
+        }
+
+        let Ok(next) = self.try_spy_at(arguments, location, fallback) else {
+            return or_else().await;
+        };
+
+        #[cfg(feature = "async")]
+        if let Some(delay) = self.returns.delay_of(&next) {
+            delay::sleep(delay).await;
+        }
+
+        next.value
     }
-}
 
-impl<A, B: PartialEq<A>> PartialEq<&[B]> for Arguments<A> {
-    fn eq(&self, other: &&[B]) -> bool {
-        *other == self.get().as_slice()
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn capture_async(&self, arguments: A, return_value: R) -> impl Future<Output = R> {
+        let location = Location::caller();
+        async move {
+            let in_flight_call = InFlightCall::start(&self.in_flight_calls);
+            self.capture_at(arguments, location);
+            in_flight_call.complete();
+            return_value
+        }
     }
-}
 
-impl<A: PartialEq<B>, B> PartialEq<Arguments<A>> for &[B] {
-    fn eq(&self, other: &Arguments<A>) -> bool {
-        other.get().as_slice() == *self
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn capture(&self, arguments: A) {
+        self.capture_at(arguments, Location::caller());
     }
-}
 
-impl<A: PartialEq<B>, B> PartialEq<Vec<B>> for Arguments<A> {
-    fn eq(&self, other: &Vec<B>) -> bool {
-        *self.get() == *other
+    fn capture_at(&self, arguments: A, location: &'static Location<'static>) {
+        self.arguments.push(arguments);
+        self.record_call(location);
     }
-}
 
-impl<A, B: PartialEq<A>> PartialEq<Arguments<A>> for Vec<B> {
-    fn eq(&self, other: &Arguments<A>) -> bool {
-        *self == *other.get()
+    fn record_call(&self, location: &'static Location<'static>) {
+        self.arguments.record(CallRecord::new(location));
+        self.call_count.fetch_add(1, Ordering::Relaxed);
+
+        let qualified_name = self
+            .diagnostics
+            .get()
+            .and_then(Diagnostics::qualified_name)
+            .unwrap_or(self.name);
+        for sequence in self.sequences.lock().expect("mutex poisoned").iter() {
+            sequence.record(qualified_name);
+        }
     }
-}
 
-impl<A> Arguments<A> {
-    fn push(&self, arguments: A) {
-        self.captured
-            .lock()
-            .expect("mutex poisoned")
-            .push(arguments);
-        #[cfg(feature = "async")]
-        let _ = self.sender.send_blocking(());
+    #[cfg(feature = "async")]
+    #[allow(clippy::future_not_send)]
+    async fn spy_controlled(
+        &self,
+        controller: &ControllerSender<A, R>,
+        arguments: A,
+        location: &'static Location<'static>,
+    ) -> R {
+        self.capture_at((controller.clone)(&arguments), location);
+
+        let (responder, response) = async_channel::bounded(1);
+        let pending_call = PendingCall::new(arguments, responder);
+        assert!(
+            controller.sender.try_send(pending_call).is_ok(),
+            "function '{}' is controlled, but its controller was dropped",
+            self.name
+        );
+
+        response.recv().await.unwrap_or_else(|_| {
+            panic!(
+                "function '{}' call was dropped without a response",
+                self.name
+            )
+        })
     }
 
-    /// Gets the captured arguments. This returns a [`MutexGuard`] which must be dereferenced.
+    /// Records every call to this function in the [`Sequence`], by its `Trait::function` name.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self, bar: u8);
+    ///     fn foo(&self);
     /// }
     ///
-    /// fn use_trait(trait_object: &impl MyTrait) {
-    ///     trait_object.foo(10);
-    /// }
+    /// let sequence = autospy::Sequence::default();
     ///
     /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([()]);
+    /// spy.foo.in_sequence(&sequence);
+    /// spy.foo.returns.set([(), ()]);
     ///
-    /// use_trait(&spy);
+    /// spy.foo();
+    /// spy.foo();
     ///
-    /// // get will not clear the captured spy arguments
-    /// assert_eq!(vec![10], *spy.foo.arguments.get());
-    /// assert_eq!(vec![10], *spy.foo.arguments.get());
+    /// sequence.assert_order(["MyTrait::foo", "MyTrait::foo"]);
     /// ```
     #[allow(clippy::missing_panics_doc)]
-    pub fn get(&self) -> MutexGuard<'_, Vec<A>> {
-        self.captured.lock().expect("mutex poisoned")
+    pub fn in_sequence(&self, sequence: &Sequence) {
+        self.sequences
+            .lock()
+            .expect("mutex poisoned")
+            .push(sequence.clone());
     }
 
-    /// Takes the captured arguments.
+    /// Expect the function to be called exactly `times`, verified when the spy is dropped.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self, bar: u8);
-    /// }
-    ///
-    /// fn use_trait(trait_object: &impl MyTrait) {
-    ///     trait_object.foo(10);
+    ///     fn foo(&self, bar: u8) -> u8;
     /// }
     ///
     /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([()]);
-    ///
-    /// use_trait(&spy);
+    /// spy.foo.returns.set_fn(|bar| bar + 1);
+    /// spy.foo.expect_calls(2);
     ///
-    /// // take will clear the captured spy arguments
-    /// assert_eq!(vec![10], spy.foo.arguments.take());
-    /// assert!(spy.foo.arguments.take().is_empty());
+    /// assert_eq!(2, spy.foo(1));
+    /// assert_eq!(3, spy.foo(2));
     /// ```
-    #[allow(clippy::missing_panics_doc)]
-    #[must_use]
-    pub fn take(&self) -> Vec<A> {
-        std::mem::take(&mut *self.captured.lock().expect("mutex poisoned"))
+    pub fn expect_calls(&self, times: usize) {
+        self.expect(ExpectedCalls::Exactly(times));
     }
 
-    /// Returns `true` if the captured arguments contain no elements.
+    /// Expect the function to be called at least `times`, verified when the spy is dropped.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self, bar: u8);
-    /// }
-    ///
-    /// fn use_trait(trait_object: &impl MyTrait) {
-    ///     trait_object.foo(10);
+    ///     fn foo(&self);
     /// }
     ///
     /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([()]);
-    ///
-    /// assert!(spy.foo.arguments.is_empty());
+    /// spy.foo.returns.set_fn(|_| ());
+    /// spy.foo.expect_at_least(1);
     ///
-    /// use_trait(&spy);
-    ///
-    /// assert!(!spy.foo.arguments.is_empty());
+    /// spy.foo();
+    /// spy.foo();
     /// ```
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.get().is_empty()
+    pub fn expect_at_least(&self, times: usize) {
+        self.expect(ExpectedCalls::AtLeast(times));
     }
 
-    /// Returns the number of captured arguments.
+    /// Expect the function to never be called, verified when the spy is dropped.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self, bar: u8);
+    ///     fn foo(&self);
     /// }
     ///
-    /// fn use_trait(trait_object: &impl MyTrait) {
-    ///     trait_object.foo(1);
-    ///     trait_object.foo(2);
-    ///     trait_object.foo(3);
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.expect_never();
+    /// ```
+    pub fn expect_never(&self) {
+        self.expect(ExpectedCalls::Exactly(0));
+    }
+
+    /// The number of async calls to this function which have started, but not yet completed or been cancelled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Service: Send + Sync + 'static {
+    ///     async fn fetch(&self) -> u8;
     /// }
     ///
-    /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([(),(),()]);
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = ServiceSpy::default();
+    ///     let controller = spy.fetch.returns.controlled();
     ///
-    /// use_trait(&spy);
+    ///     let task = tokio::task::spawn({
+    ///         let spy = spy.clone();
+    ///         async move { spy.fetch().await }
+    ///     });
     ///
-    /// assert_eq!(3, spy.foo.arguments.len());
+    ///     let call = controller.next_call().await;
+    ///     assert_eq!(1, spy.fetch.in_flight());
+    ///
+    ///     call.respond(1);
+    ///     task.await.unwrap();
+    ///     assert_eq!(0, spy.fetch.in_flight());
+    /// })
     /// ```
+    #[allow(clippy::missing_panics_doc)]
     #[must_use]
-    pub fn len(&self) -> usize {
-        self.get().len()
+    pub fn in_flight(&self) -> usize {
+        self.in_flight_calls
+            .lock()
+            .expect("mutex poisoned")
+            .in_flight()
     }
 
-    /// Clear the captured arguments.
+    /// The most async calls to this function that have been in flight at the same time.
     ///
     /// # Examples
     /// ```rust
-    /// #[autospy::autospy]
-    /// trait MyTrait {
-    ///     fn foo(&self, bar: u8);
-    /// }
+    /// use std::time::Duration;
     ///
-    /// fn use_trait(trait_object: &impl MyTrait) {
-    ///     trait_object.foo(10);
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Service: Send + Sync + 'static {
+    ///     async fn fetch(&self, id: u8) -> u8;
     /// }
     ///
-    /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([()]);
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = ServiceSpy::default();
+    ///     spy.fetch.returns.set_fn(|id| *id);
+    ///     spy.fetch.returns.delay_all(Duration::from_millis(10));
     ///
-    /// use_trait(&spy);
+    ///     let (first, second) = tokio::join!(spy.fetch(1), spy.fetch(2));
+    ///     assert_eq!((1, 2), (first, second));
     ///
-    /// assert_eq!([10], spy.foo.arguments);
-    /// // clear the captured spy arguments
-    /// spy.foo.arguments.clear();
-    /// assert!(spy.foo.arguments.take().is_empty());
+    ///     assert_eq!(2, spy.fetch.max_concurrent_calls());
+    /// })
     /// ```
     #[allow(clippy::missing_panics_doc)]
-    pub fn clear(&self) {
-        let _ = self.take();
+    #[must_use]
+    pub fn max_concurrent_calls(&self) -> usize {
+        self.in_flight_calls
+            .lock()
+            .expect("mutex poisoned")
+            .max_concurrent()
     }
 
-    /// Asynchronously takes all captured arguments when the spy is used.
-    /// Enabled by default via the **async** feature.
+    /// The [`CallOutcome`] of each async call to this function, in the order the calls were made.
     ///
     /// # Examples
     /// ```rust
+    /// use autospy::CallOutcome;
     /// use std::time::Duration;
     ///
     /// #[autospy::autospy]
     /// #[async_trait::async_trait]
-    /// trait MyTrait: Send + 'static {
-    ///     async fn foo(&self, argument: &str);
+    /// trait Service: Send + Sync + 'static {
+    ///     async fn fetch(&self) -> u8;
     /// }
     ///
-    /// async fn use_async_trait(x: impl MyTrait) {
-    ///     tokio::task::spawn(async move {
-    ///         tokio::time::sleep(Duration::from_millis(100)).await;
-    ///         x.foo("async used after some time!").await;
-    ///     });
+    /// async fn fetch_with_timeout(service: &impl Service) -> Option<u8> {
+    ///     tokio::time::timeout(Duration::from_millis(10), service.fetch()).await.ok()
     /// }
     ///
     /// tokio::runtime::Runtime::new().unwrap().block_on(async {
-    ///     let spy = MyTraitSpy::default();
-    ///     spy.foo.returns.set([()]);
+    ///     let spy = ServiceSpy::default();
+    ///     spy.fetch.returns.set_fn(|_| 1);
+    ///     spy.fetch.returns.delay_all(Duration::from_secs(1));
     ///
-    ///     use_async_trait(spy.clone()).await;
-    ///     // spy not used yet
-    ///     assert!(spy.foo.arguments.take().is_empty());
-    ///     // spy used after 100ms
-    ///     assert_eq!("async used after some time!", spy.foo.arguments.recv().await[0])
+    ///     assert_eq!(None, fetch_with_timeout(&spy).await);
+    ///
+    ///     // the timeout dropped the future of the call
+    ///     assert_eq!([CallOutcome::Cancelled], spy.fetch.call_outcomes().as_slice());
     /// })
     /// ```
     #[allow(clippy::missing_panics_doc)]
-    #[cfg(feature = "async")]
-    pub async fn recv(&self) -> Vec<A> {
-        self.receiver.recv().await.expect("no more messages");
-        self.take()
+    #[must_use]
+    pub fn call_outcomes(&self) -> Vec<CallOutcome> {
+        self.in_flight_calls
+            .lock()
+            .expect("mutex poisoned")
+            .outcomes()
     }
-}
 
-/// # Panics
-/// Panics if not enough return values have been set for the number of times the function is called.
-/// ```should_panic
-/// #[autospy::autospy]
-/// trait MyTrait {
-///     fn foo(&self);
-/// }
-///
-/// let spy = MyTraitSpy::default();
-///
-/// spy.foo()  // panics because we haven't set a return value
-/// ```
-///
-/// Panics if too many return values have been set for the number of times the function is called.
-/// ```should_panic
-/// #[autospy::autospy]
-/// trait MyTrait {
-///     fn foo(&self);
-/// }
-///
-/// let spy = MyTraitSpy::default();
+    /// Clears the captured [`arguments`](Self::arguments), the [`returns`](Self::returns), any call count expectations
+    /// and the async [`call_outcomes`](Self::call_outcomes).
+    /// Any fallback return value is kept.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8) -> u8;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([1, 2]);
+    ///
+    /// assert_eq!(1, spy.foo(10));
+    ///
+    /// spy.foo.reset();
+    ///
+    /// assert!(spy.foo.arguments.is_empty());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn reset(&self) {
+        self.arguments.clear();
+        self.returns.clear();
+        self.call_count.store(0, Ordering::Relaxed);
+        *self.expected_calls.lock().expect("mutex poisoned") = None;
+        self.in_flight_calls.lock().expect("mutex poisoned").clear();
+    }
+
+    /// Asserts all return values set so far have been used.
+    ///
+    /// # Panics
+    /// Panics if any return values set have not been used.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self) -> u8;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([1]);
+    ///
+    /// assert_eq!(1, spy.foo());
+    ///
+    /// spy.foo.checkpoint();
+    /// ```
+    #[track_caller]
+    pub fn checkpoint(&self) {
+        let unused_count = self.returns.queue_len();
+        assert!(
+            unused_count == 0,
+            "function '{}' had {} unused return values at checkpoint{}",
+            self.name,
+            unused_count,
+            self.details()
+        );
+    }
+
+    /// Asserts there are no captured [`arguments`](Self::arguments) that have not been taken.
+    ///
+    /// # Panics
+    /// Panics if the function has captured arguments.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// spy.foo(10);
+    ///
+    /// assert_eq!(vec![10], spy.foo.arguments.take());
+    /// spy.foo.assert_no_more_interactions();
+    /// ```
+    #[track_caller]
+    pub fn assert_no_more_interactions(&self) {
+        let interactions = self.arguments.len();
+        assert!(
+            interactions == 0,
+            "function '{}' had {} interaction(s) that were not taken",
+            self.name,
+            interactions
+        );
+    }
+
+    #[doc(hidden)]
+    pub fn set_diagnostics(
+        &self,
+        trait_name: &'static str,
+        qualified_name: &'static str,
+        format_arguments: Option<fn(&A) -> String>,
+        format_return: Option<fn(&R) -> String>,
+    ) {
+        let _ = self.diagnostics.set(Diagnostics::new(
+            trait_name,
+            qualified_name,
+            format_arguments,
+            format_return,
+        ));
+    }
+
+    fn expect(&self, expected_calls: ExpectedCalls) {
+        *self.expected_calls.lock().expect("mutex poisoned") = Some(expected_calls);
+    }
+
+    /// Renders the captured arguments of each call, used by the generated `autospy_render_interactions`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn interactions(&self) -> String {
+        let unknown = Diagnostics::default();
+        let diagnostics = self.diagnostics.get().unwrap_or(&unknown);
+        diagnostics.interactions(self.name, &self.arguments.get())
+    }
+
+    fn details(&self) -> String {
+        let unknown = Diagnostics::default();
+        let diagnostics = self.diagnostics.get().unwrap_or(&unknown);
+        self.returns.with_unused(|unused_returns| {
+            diagnostics.details(
+                self.returns.set_location(),
+                unused_returns,
+                &self.arguments.get(),
+            )
+        })
+    }
+}
+
+///
+/// Arguments implements [`PartialEq`] for `[A]`, `&[A]` and `Vec<A>`.
+///
+/// # Examples
+/// ```rust
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self, bar: u8);
+/// }
+///
+/// fn use_trait(trait_object: &impl MyTrait) {
+///     trait_object.foo(10)
+/// }
+///
+/// let spy = MyTraitSpy::default();
+/// spy.foo.returns.set([()]);
+///
+/// use_trait(&spy);
+///
+/// // all valid PartialEq implementations
+/// assert_eq!([10], spy.foo.arguments);
+/// assert_eq!([10].as_slice(), spy.foo.arguments);
+/// assert_eq!(vec![10], spy.foo.arguments);
+/// ```
+pub struct Arguments<A> {
+    captured: Arc<Mutex<Vec<A>>>,
+    records: Arc<Mutex<Vec<CallRecord>>>,
+    notifier: Arc<Notifier>,
+    name: &'static str,
+}
+
+impl<A: Debug> Debug for Arguments<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.captured.lock() {
+            Ok(captured) => captured.fmt(f),
+            Err(_) => write!(f, "mutex poisoned"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<A: serde::Serialize> serde::Serialize for Arguments<A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<A> Clone for Arguments<A> {
+    fn clone(&self) -> Self {
+        Self {
+            captured: self.captured.clone(),
+            records: self.records.clone(),
+            notifier: self.notifier.clone(),
+            name: self.name,
+        }
+    }
+}
+
+impl<A> Default for Arguments<A> {
+    fn default() -> Self {
+        Self::from("")
+    }
+}
+
+impl<A> From<&'static str> for Arguments<A> {
+    fn from(name: &'static str) -> Self {
+        Self {
+            captured: Arc::new(Mutex::new(Vec::new())),
+            records: Arc::new(Mutex::new(Vec::new())),
+            notifier: Arc::new(Notifier::default()),
+            name,
+        }
+    }
+}
+
+impl_slice_partial_eq!(Arguments);
+
+impl<A> Arguments<A> {
+    pub(crate) fn push(&self, arguments: A) {
+        self.captured
+            .lock()
+            .expect("mutex poisoned")
+            .push(arguments);
+        self.notifier.notify();
+    }
+
+    pub(crate) const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(crate) fn record(&self, record: CallRecord) {
+        self.records.lock().expect("mutex poisoned").push(record);
+    }
+
+    /// Gets the captured arguments. This returns a [`MutexGuard`] which must be dereferenced.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// // get will not clear the captured spy arguments
+    /// assert_eq!(vec![10], *spy.foo.arguments.get());
+    /// assert_eq!(vec![10], *spy.foo.arguments.get());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get(&self) -> MutexGuard<'_, Vec<A>> {
+        self.captured.lock().expect("mutex poisoned")
+    }
+
+    /// Takes the captured arguments.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// // take will clear the captured spy arguments
+    /// assert_eq!(vec![10], spy.foo.arguments.take());
+    /// assert!(spy.foo.arguments.take().is_empty());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn take(&self) -> Vec<A> {
+        std::mem::take(&mut *self.captured.lock().expect("mutex poisoned"))
+    }
+
+    /// Returns `true` if the captured arguments contain no elements.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// assert!(spy.foo.arguments.is_empty());
+    ///
+    /// use_trait(&spy);
+    ///
+    /// assert!(!spy.foo.arguments.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.get().is_empty()
+    }
+
+    /// Returns the number of captured arguments.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(1);
+    ///     trait_object.foo(2);
+    ///     trait_object.foo(3);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([(),(),()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// assert_eq!(3, spy.foo.arguments.len());
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.get().len()
+    }
+
+    /// Gets a [`CallRecord`] of where, when and on which thread each call was made, in the order the calls were made.
+    /// Records are kept when the captured arguments are taken, until they are [cleared](Self::clear).
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// let line = line!() + 1;
+    /// spy.foo(10);
+    ///
+    /// let calls = spy.foo.arguments.calls();
+    /// assert_eq!(line, calls[0].location.line());
+    /// assert_eq!(std::thread::current().id(), calls[0].thread.id());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn calls(&self) -> Vec<CallRecord> {
+        self.records.lock().expect("mutex poisoned").clone()
+    }
+
+    /// Clear the captured arguments and their [call records](Self::calls).
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: u8);
+    /// }
+    ///
+    /// fn use_trait(trait_object: &impl MyTrait) {
+    ///     trait_object.foo(10);
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([()]);
+    ///
+    /// use_trait(&spy);
+    ///
+    /// assert_eq!([10], spy.foo.arguments);
+    /// // clear the captured spy arguments
+    /// spy.foo.arguments.clear();
+    /// assert!(spy.foo.arguments.take().is_empty());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn clear(&self) {
+        let _ = self.take();
+        self.records.lock().expect("mutex poisoned").clear();
+    }
+
+    /// Asynchronously takes all captured arguments, waiting until the spy is used if none have been captured
+    /// since they were last taken. Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait: Send + 'static {
+    ///     async fn foo(&self, argument: &str);
+    /// }
+    ///
+    /// async fn use_async_trait(x: impl MyTrait) {
+    ///     tokio::task::spawn(async move {
+    ///         tokio::time::sleep(Duration::from_millis(100)).await;
+    ///         x.foo("async used after some time!").await;
+    ///     });
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///     spy.foo.returns.set([()]);
+    ///
+    ///     use_async_trait(spy.clone()).await;
+    ///     // spy not used yet
+    ///     assert!(spy.foo.arguments.take().is_empty());
+    ///     // spy used after 100ms
+    ///     assert_eq!("async used after some time!", spy.foo.arguments.recv().await[0])
+    /// })
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[cfg(feature = "async")]
+    pub async fn recv(&self) -> Vec<A> {
+        self.recv_with(|captured| (!captured.is_empty()).then(|| std::mem::take(captured)))
+            .await
+    }
+
+    /// Asynchronously takes the first `n` captured arguments, once the spy has been called at least `n` times.
+    /// Any further captured arguments are kept. Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait: Send + Sync + 'static {
+    ///     async fn foo(&self, argument: u8);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///     spy.foo.returns.set_fn(|_| ());
+    ///
+    ///     tokio::task::spawn({
+    ///         let spy = spy.clone();
+    ///         async move {
+    ///             for argument in 1..=3 {
+    ///                 spy.foo(argument).await;
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     assert_eq!(vec![1, 2], spy.foo.arguments.recv_n(2).await);
+    /// })
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn recv_n(&self, n: usize) -> Vec<A> {
+        self.recv_with(|captured| (captured.len() >= n).then(|| captured.drain(..n).collect()))
+            .await
+    }
+
+    /// Asynchronously takes the first captured arguments matching the predicate, once the spy has been called with them.
+    /// Any other captured arguments are kept. Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Publisher: Send + Sync + 'static {
+    ///     async fn publish(&self, topic: &str);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = PublisherSpy::default();
+    ///     spy.publish.returns.set_fn(|_| ());
+    ///
+    ///     tokio::task::spawn({
+    ///         let spy = spy.clone();
+    ///         async move {
+    ///             spy.publish("users").await;
+    ///             spy.publish("orders").await;
+    ///         }
+    ///     });
+    ///
+    ///     assert_eq!("orders", spy.publish.arguments.recv_until(|topic| topic == "orders").await);
+    ///     assert_eq!(["users"], spy.publish.arguments);
+    /// })
+    /// ```
+    #[allow(clippy::future_not_send)]
+    #[cfg(feature = "async")]
+    pub async fn recv_until(&self, predicate: impl Fn(&A) -> bool) -> A {
+        self.recv_with(|captured| {
+            captured
+                .iter()
+                .position(&predicate)
+                .map(|index| captured.remove(index))
+        })
+        .await
+    }
+
+    /// Asynchronously takes the first captured arguments, once the spy has been called.
+    /// Any further captured arguments are kept. Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait: Send + Sync + 'static {
+    ///     async fn foo(&self, argument: u8);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///     spy.foo.returns.set_fn(|_| ());
+    ///
+    ///     spy.foo(1).await;
+    ///     spy.foo(2).await;
+    ///
+    ///     assert_eq!(1, spy.foo.arguments.next_call().await);
+    ///     assert_eq!(2, spy.foo.arguments.next_call().await);
+    /// })
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn next_call(&self) -> A {
+        self.recv_with(|captured| (!captured.is_empty()).then(|| captured.remove(0)))
+            .await
+    }
+
+    /// A [`Stream`](futures_core::Stream) which takes each captured argument in the order the spy was called,
+    /// waiting for further calls once all captured arguments have been taken. The stream never ends.
+    /// Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use futures::StreamExt;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait: Send + Sync + 'static {
+    ///     async fn foo(&self, argument: u8);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///     spy.foo.returns.set_fn(|_| ());
+    ///
+    ///     tokio::task::spawn({
+    ///         let spy = spy.clone();
+    ///         async move {
+    ///             for argument in 1..=3 {
+    ///                 spy.foo(argument).await;
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     let doubled: Vec<u8> = spy.foo.arguments.stream().map(|argument| argument * 2).take(3).collect().await;
+    ///     assert_eq!(vec![2, 4, 6], doubled);
+    /// })
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn stream(&self) -> impl futures_core::Stream<Item = A> + Unpin + use<A> {
+        ArgumentsStream::new(self.clone())
+    }
+
+    /// Like [`recv_n`](Self::recv_n), but waits for at most `timeout`.
+    /// Enabled by default via the **async** feature.
+    ///
+    /// # Panics
+    /// Panics if the spy has not been called `n` times before the timeout, naming the function and the arguments
+    /// captured so far.
+    ///
+    /// # Examples
+    /// ```rust,should_panic
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait {
+    ///     async fn foo(&self, argument: u8);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///     spy.foo.returns.set([()]);
+    ///
+    ///     spy.foo(1).await;
+    ///
+    ///     // panics because the spy was only called once
+    ///     spy.foo.arguments.recv_n_timeout(2, Duration::from_millis(10)).await;
+    /// })
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn recv_n_timeout(&self, n: usize, timeout: Duration) -> Vec<A>
+    where
+        A: Debug,
+    {
+        self.with_timeout(self.recv_n(n), timeout, &format!("{n} call(s)"))
+            .await
+    }
+
+    /// Like [`recv_until`](Self::recv_until), but waits for at most `timeout`.
+    /// Enabled by default via the **async** feature.
+    ///
+    /// # Panics
+    /// Panics if the spy has not been called with matching arguments before the timeout, naming the function and the
+    /// arguments captured so far.
+    ///
+    /// # Examples
+    /// ```rust,should_panic
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Publisher {
+    ///     async fn publish(&self, topic: &str);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = PublisherSpy::default();
+    ///     spy.publish.returns.set([()]);
+    ///
+    ///     spy.publish("users").await;
+    ///
+    ///     // panics because the spy was never called with "orders"
+    ///     spy.publish
+    ///         .arguments
+    ///         .recv_until_timeout(|topic| topic == "orders", Duration::from_millis(10))
+    ///         .await;
+    /// })
+    /// ```
+    #[allow(clippy::future_not_send)]
+    #[cfg(feature = "async")]
+    pub async fn recv_until_timeout(&self, predicate: impl Fn(&A) -> bool, timeout: Duration) -> A
+    where
+        A: Debug,
+    {
+        self.with_timeout(self.recv_until(predicate), timeout, "a matching call")
+            .await
+    }
+
+    /// Like [`next_call`](Self::next_call), but waits for at most `timeout`.
+    /// Enabled by default via the **async** feature.
+    ///
+    /// # Panics
+    /// Panics if the spy has not been called before the timeout, naming the function.
+    ///
+    /// # Examples
+    /// ```rust,should_panic
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait MyTrait {
+    ///     async fn foo(&self, argument: u8);
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = MyTraitSpy::default();
+    ///
+    ///     // panics because the spy was never called
+    ///     spy.foo.arguments.next_call_timeout(Duration::from_millis(10)).await;
+    /// })
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn next_call_timeout(&self, timeout: Duration) -> A
+    where
+        A: Debug,
+    {
+        self.with_timeout(self.next_call(), timeout, "a call").await
+    }
+
+    #[cfg(feature = "async")]
+    async fn recv_with<T>(&self, mut take: impl FnMut(&mut Vec<A>) -> Option<T>) -> T {
+        std::future::poll_fn(|context| self.poll_take(context, &mut take)).await
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_next_call(&self, context: &Context<'_>) -> Poll<A> {
+        self.poll_take(context, |captured| {
+            (!captured.is_empty()).then(|| captured.remove(0))
+        })
+    }
+
+    #[cfg(feature = "async")]
+    fn poll_take<T>(
+        &self,
+        context: &Context<'_>,
+        take: impl FnOnce(&mut Vec<A>) -> Option<T>,
+    ) -> Poll<T> {
+        let mut captured = self.get();
+        take(&mut captured).map_or_else(
+            || {
+                self.notifier.register(context.waker());
+                Poll::Pending
+            },
+            Poll::Ready,
+        )
+    }
+
+    // the timeout sleeps on the shared timer, or on tokio's inside a tokio runtime, and is cancelled
+    // as soon as the future completes
+    #[cfg(feature = "async")]
+    async fn with_timeout<T>(
+        &self,
+        future: impl Future<Output = T>,
+        timeout: Duration,
+        waiting_for: &str,
+    ) -> T
+    where
+        A: Debug,
+    {
+        delay::timeout(timeout, future)
+            .await
+            .unwrap_or_else(|| self.timed_out(timeout, waiting_for))
+    }
+
+    /// Blocks the current thread until the spy has been called at least `n` times, then takes the first `n`
+    /// captured arguments. Any further captured arguments are kept. This does not need an async runtime, so can
+    /// wait for calls made from other threads.
+    ///
+    /// # Panics
+    /// Panics if the spy has not been called `n` times before the timeout, naming the function and the arguments
+    /// captured so far.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// trait Worker: Send + Sync + 'static {
+    ///     fn process(&self, job: u8);
+    /// }
+    ///
+    /// let spy = WorkerSpy::default();
+    /// spy.process.returns.set_fn(|_| ());
+    ///
+    /// for job in 1..=3 {
+    ///     let spy = spy.clone();
+    ///     std::thread::spawn(move || spy.process(job));
+    /// }
+    ///
+    /// let mut jobs = spy.process.arguments.wait_for(3, Duration::from_secs(5));
+    /// jobs.sort();
+    /// assert_eq!(vec![1, 2, 3], jobs);
+    /// ```
+    #[must_use]
+    pub fn wait_for(&self, n: usize, timeout: Duration) -> Vec<A>
+    where
+        A: Debug,
+    {
+        self.wait_with(timeout, &format!("{n} call(s)"), |captured| {
+            (captured.len() >= n).then(|| captured.drain(..n).collect())
+        })
+    }
+
+    /// Blocks the current thread until the spy has been called with arguments matching the predicate, then takes
+    /// them. Any other captured arguments are kept. This does not need an async runtime, so can wait for calls
+    /// made from other threads.
+    ///
+    /// # Panics
+    /// Panics if the spy has not been called with matching arguments before the timeout, naming the function and
+    /// the arguments captured so far.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// trait Consumer: Send + Sync + 'static {
+    ///     fn consume(&self, message: String);
+    /// }
+    ///
+    /// let spy = ConsumerSpy::default();
+    /// spy.consume.returns.set_fn(|_| ());
+    ///
+    /// let (sender, receiver) = std::sync::mpsc::channel::<String>();
+    /// std::thread::spawn({
+    ///     let spy = spy.clone();
+    ///     move || receiver.into_iter().for_each(|message| spy.consume(message))
+    /// });
+    ///
+    /// sender.send("hello".to_string()).unwrap();
+    /// sender.send("stop".to_string()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "stop",
+    ///     spy.consume.arguments.wait_until(|message| message == "stop", Duration::from_secs(5))
+    /// );
+    /// assert_eq!(["hello"], spy.consume.arguments);
+    /// ```
+    #[must_use]
+    pub fn wait_until(&self, predicate: impl Fn(&A) -> bool, timeout: Duration) -> A
+    where
+        A: Debug,
+    {
+        self.wait_with(timeout, "a matching call", |captured| {
+            captured
+                .iter()
+                .position(&predicate)
+                .map(|index| captured.remove(index))
+        })
+    }
+
+    fn wait_with<T>(
+        &self,
+        timeout: Duration,
+        waiting_for: &str,
+        mut take: impl FnMut(&mut Vec<A>) -> Option<T>,
+    ) -> T
+    where
+        A: Debug,
+    {
+        let deadline = Instant::now() + timeout;
+        let mut captured = self.get();
+        loop {
+            if let Some(value) = take(&mut captured) {
+                return value;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                drop(captured);
+                self.timed_out(timeout, waiting_for);
+            }
+            captured = self.notifier.wait_timeout(captured, remaining);
+        }
+    }
+
+    fn timed_out(&self, timeout: Duration, waiting_for: &str) -> !
+    where
+        A: Debug,
+    {
+        let captured = format!("{:?}", *self.get());
+        panic!(
+            "function '{}' timed out after {:?} waiting for {}, captured so far: {}",
+            self.name, timeout, waiting_for, captured
+        )
+    }
+}
+
+/// # Panics
+/// Panics if not enough return values have been set for the number of times the function is called.
+/// ```should_panic
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self);
+/// }
+///
+/// let spy = MyTraitSpy::default();
+///
+/// spy.foo()  // panics because we haven't set a return value
+/// ```
+///
+/// Panics if too many return values have been set for the number of times the function is called.
+/// ```should_panic
+/// #[autospy::autospy]
+/// trait MyTrait {
+///     fn foo(&self);
+/// }
+///
+/// let spy = MyTraitSpy::default();
 ///
 /// spy.foo.returns.set([(), ()]);
 ///
@@ -425,75 +1424,506 @@ impl<A> Arguments<A> {
 ///
 /// spy.foo() // will always return ()
 /// ```
-pub struct Returns<A, R> {
+pub struct Returns<A, R, H: ?Sized = ()> {
+    name: &'static str,
     queue: Arc<Mutex<ReturnQueue<A, R>>>,
+    rules: Arc<Mutex<Vec<ReturnRule<A, R>>>>,
+    fallback: Arc<Mutex<Option<GetReturn<A, R>>>>,
+    mutable_fn: Arc<Mutex<Option<Box<H>>>>,
+    #[cfg(feature = "async")]
+    controller: Arc<Mutex<Option<ControllerSender<A, R>>>>,
+    #[cfg(feature = "async")]
+    delays: Arc<Mutex<Delays>>,
     set_count: Arc<AtomicUsize>,
+    rule_count: Arc<AtomicUsize>,
+    set_location: Arc<Mutex<Option<&'static Location<'static>>>>,
 }
 
-impl<A, R> Clone for Returns<A, R> {
-    fn clone(&self) -> Self {
-        Self {
-            queue: Arc::clone(&self.queue),
-            set_count: Arc::clone(&self.set_count),
-        }
+impl<A, R, H: ?Sized> Clone for Returns<A, R, H> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            queue: Arc::clone(&self.queue),
+            rules: Arc::clone(&self.rules),
+            fallback: Arc::clone(&self.fallback),
+            mutable_fn: Arc::clone(&self.mutable_fn),
+            #[cfg(feature = "async")]
+            controller: Arc::clone(&self.controller),
+            #[cfg(feature = "async")]
+            delays: Arc::clone(&self.delays),
+            set_count: Arc::clone(&self.set_count),
+            rule_count: Arc::clone(&self.rule_count),
+            set_location: Arc::clone(&self.set_location),
+        }
+    }
+}
+
+impl<A, R, H: ?Sized> Default for Returns<A, R, H> {
+    fn default() -> Self {
+        Self {
+            name: "",
+            queue: Arc::new(Mutex::new(ReturnQueue::Finite(VecDeque::new()))),
+            rules: Arc::new(Mutex::new(Vec::new())),
+            fallback: Arc::new(Mutex::new(None)),
+            mutable_fn: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "async")]
+            controller: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "async")]
+            delays: Arc::new(Mutex::new(Delays::default())),
+            set_count: Arc::new(AtomicUsize::new(0)),
+            rule_count: Arc::new(AtomicUsize::new(0)),
+            set_location: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<A, R, H: ?Sized> Returns<A, R, H> {
+    /// Set the spy return values.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self) -> u8;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set([0,1,2]);
+    ///
+    /// // return values are returned in order from left to right
+    /// assert_eq!(0, spy.foo());
+    /// assert_eq!(1, spy.foo());
+    /// assert_eq!(2, spy.foo());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[track_caller]
+    pub fn set<I: IntoIterator<Item = R>>(&self, values: I) {
+        let queue: ReturnQueue<_, _> = values.into_iter().collect();
+        self.set_count.fetch_add(queue.len(), Ordering::Relaxed);
+        *self.queue.lock().expect("mutex poisoned") = queue;
+        *self.set_location.lock().expect("mutex poisoned") = Some(Location::caller());
+    }
+
+    /// Set the spy return values of an async spy function, each returned after its delay.
+    /// Each delay is used by the call that returns its value, after which calls use the delay from
+    /// [`delay_all`](Self::delay_all), if any. Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Service {
+    ///     async fn fetch(&self) -> u8;
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = ServiceSpy::default();
+    ///     spy.fetch.returns.set_with_delay([(Duration::from_millis(50), 1), (Duration::ZERO, 2)]);
+    ///
+    ///     let start = Instant::now();
+    ///     assert_eq!(1, spy.fetch().await);
+    ///     assert!(start.elapsed() >= Duration::from_millis(50));
+    ///     assert_eq!(2, spy.fetch().await);
+    /// })
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[cfg(feature = "async")]
+    #[track_caller]
+    pub fn set_with_delay<I: IntoIterator<Item = (Duration, R)>>(&self, values: I) {
+        let (delays, values): (Vec<_>, Vec<_>) = values.into_iter().unzip();
+        self.delays.lock().expect("mutex poisoned").set(delays);
+        self.set(values);
+    }
+
+    /// Delay every return of an async spy function, to simulate a slow service.
+    /// Delays from [`set_with_delay`](Self::set_with_delay) take precedence while they last.
+    /// Enabled by default via the **async** feature. With the **tokio** feature, calls made inside a
+    /// tokio runtime use `tokio::time`, so paused time is respected.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Service: Send + Sync + 'static {
+    ///     async fn fetch(&self) -> u8;
+    /// }
+    ///
+    /// async fn fetch_with_timeout(service: &impl Service) -> Option<u8> {
+    ///     tokio::time::timeout(Duration::from_millis(10), service.fetch()).await.ok()
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = ServiceSpy::default();
+    ///     spy.fetch.returns.set_fn(|_| 1);
+    ///     spy.fetch.returns.delay_all(Duration::from_secs(1));
+    ///
+    ///     assert_eq!(None, fetch_with_timeout(&spy).await);
+    /// })
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[cfg(feature = "async")]
+    pub fn delay_all(&self, delay: Duration) {
+        self.delays.lock().expect("mutex poisoned").set_all(delay);
+    }
+
+    /// Set a return function for the spy that can use the function [arguments](Arguments). When set, the spy will always return using this function.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, bar: &str) -> usize;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set_fn(|bar| bar.len());
+    ///
+    /// assert_eq!(3, spy.foo("baz"));
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_fn(&self, getter: impl FnMut(&A) -> R + Send + 'static) {
+        *self.queue.lock().expect("mutex poisoned") = ReturnQueue::Infinite(Box::new(getter));
+    }
+
+    #[doc(hidden)]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_boxed_fn(&self, getter: Box<H>) {
+        *self.mutable_fn.lock().expect("mutex poisoned") = Some(getter);
     }
-}
 
-impl<A, R> Default for Returns<A, R> {
-    fn default() -> Self {
-        Self {
-            queue: Arc::new(Mutex::new(ReturnQueue::Finite(VecDeque::new()))),
-            set_count: Arc::new(AtomicUsize::new(0)),
-        }
+    #[doc(hidden)]
+    pub fn call_fn_mut<T>(&self, call: impl FnOnce(&mut H) -> T) -> Option<T> {
+        self.mutable_fn
+            .lock()
+            .expect("mutex poisoned")
+            .as_deref_mut()
+            .map(call)
     }
-}
 
-impl<A, R> Returns<A, R> {
-    /// Set the spy return values.
+    /// Control the responses of an async spy function. Every call stays pending until the test
+    /// responds to it through the returned [`Controller`], and any other return values are ignored.
+    /// The arguments are captured when the call is made, and a copy is given to the [`PendingCall`].
+    /// Enabled by default via the **async** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// #[autospy::autospy]
+    /// #[async_trait::async_trait]
+    /// trait Client: Send + Sync + 'static {
+    ///     async fn fetch(&self, id: u8) -> String;
+    /// }
+    ///
+    /// async fn fetch_with_timeout(client: &impl Client) -> Option<String> {
+    ///     tokio::time::timeout(Duration::from_millis(10), client.fetch(1)).await.ok()
+    /// }
+    ///
+    /// tokio::runtime::Runtime::new().unwrap().block_on(async {
+    ///     let spy = ClientSpy::default();
+    ///     let _controller = spy.fetch.returns.controlled();
+    ///
+    ///     // the call is never responded to, so times out
+    ///     assert_eq!(None, fetch_with_timeout(&spy).await);
+    /// })
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub fn controlled(&self) -> Controller<A, R>
+    where
+        A: Clone,
+    {
+        let (sender, receiver) = async_channel::unbounded();
+        *self.controller.lock().expect("mutex poisoned") = Some(ControllerSender {
+            sender,
+            clone: A::clone,
+        });
+        Controller::new(receiver)
+    }
+
+    /// Set a fallback return function for the spy, used whenever no other return value is available,
+    /// instead of panicking.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self) -> u8;
+    ///     fn foo(&self, bar: u8) -> Result<u8, String>;
     /// }
     ///
     /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set([0,1,2]);
+    /// spy.foo.returns.set([Err("first".to_string())]);
+    /// spy.foo.returns.set_fallback_fn(|bar| Ok(*bar));
     ///
-    /// // return values are returned in order from left to right
-    /// assert_eq!(0, spy.foo());
-    /// assert_eq!(1, spy.foo());
-    /// assert_eq!(2, spy.foo());
+    /// assert_eq!(Err("first".to_string()), spy.foo(1));
+    /// assert_eq!(Ok(2), spy.foo(2));
     /// ```
     #[allow(clippy::missing_panics_doc)]
-    pub fn set<I: IntoIterator<Item = R>>(&self, values: I) {
-        let queue: ReturnQueue<_, _> = values.into_iter().collect();
-        self.set_count.fetch_add(queue.len(), Ordering::Relaxed);
-        *self.queue.lock().expect("mutex poisoned") = queue;
+    pub fn set_fallback_fn(&self, getter: impl FnMut(&A) -> R + Send + 'static) {
+        *self.fallback.lock().expect("mutex poisoned") = Some(Box::new(getter));
     }
 
-    /// Set a return function for the spy that can use the function [arguments](Arguments). When set, the spy will always return using this function.
+    /// Use the [`Default`] value as the fallback return value, whenever no other return value is available,
+    /// instead of panicking.
     ///
     /// # Examples
     /// ```rust
     /// #[autospy::autospy]
     /// trait MyTrait {
-    ///     fn foo(&self, bar: &str) -> usize;
+    ///     fn foo(&self) -> Option<u8>;
     /// }
     ///
     /// let spy = MyTraitSpy::default();
-    /// spy.foo.returns.set_fn(|bar| bar.len());
+    /// spy.foo.returns.use_default();
     ///
-    /// assert_eq!(3, spy.foo("baz"));
+    /// assert_eq!(None, spy.foo());
     /// ```
-    #[allow(clippy::missing_panics_doc)]
-    pub fn set_fn(&self, getter: impl FnMut(&A) -> R + Send + 'static) {
-        *self.queue.lock().expect("mutex poisoned") = ReturnQueue::Infinite(Box::new(getter));
+    pub fn use_default(&self)
+    where
+        R: Default,
+    {
+        self.set_fallback_fn(|_| R::default());
+    }
+
+    /// Used by `nice` spies, the same as [`use_default`](Self::use_default) with an error naming the
+    /// function when its return type does not implement [`Default`].
+    #[doc(hidden)]
+    pub fn use_nice_default(&self)
+    where
+        R: NiceReturn,
+    {
+        self.set_fallback_fn(|_| R::nice_return());
+    }
+
+    /// Start a conditional return rule for the spy. Rules are checked in the order they were added
+    /// before any values from [`set`](Self::set) or [`set_fn`](Self::set_fn), which act as the fallback
+    /// when no rule matches the [arguments](Arguments).
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, id: u8) -> &'static str;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.when(|id| *id == 3).then_return("three");
+    /// spy.foo.returns.when(|id| *id == 4).then_return_once("four");
+    /// spy.foo.returns.set_fn(|_| "other");
+    ///
+    /// assert_eq!("three", spy.foo(3));
+    /// assert_eq!("four", spy.foo(4));
+    /// assert_eq!("other", spy.foo(4));
+    /// assert_eq!("three", spy.foo(3));
+    /// assert_eq!("other", spy.foo(1));
+    /// ```
+    pub fn when(&self, predicate: impl Fn(&A) -> bool + Send + 'static) -> When<'_, A, R, H> {
+        When {
+            returns: self,
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Replay the return values saved by a spy created with `recording`. Each call must be made with the
+    /// same arguments as the recorded call, otherwise the spy panics showing the recorded and actual
+    /// arguments. Unused recorded return values panic when the spy is dropped, the same as values from
+    /// [`set`](Self::set). Enabled via the **serde** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait Gateway {
+    ///     fn fetch(&self, id: u32) -> String;
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("autospy_replay_from_example.json");
+    /// std::fs::write(&path, r#"{ "fetch": [{ "arguments": 1, "returns": "alice" }] }"#).unwrap();
+    ///
+    /// let spy = GatewaySpy::default();
+    /// spy.fetch.returns.replay_from(&path);
+    ///
+    /// assert_eq!("alice", spy.fetch(1));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the recording could not be read, or has no interactions for the function.
+    #[cfg(feature = "serde")]
+    #[track_caller]
+    pub fn replay_from(&self, path: impl AsRef<std::path::Path>)
+    where
+        A: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let replay = Replay::load(self.name, path.as_ref());
+        self.set_count.fetch_add(replay.len(), Ordering::Relaxed);
+        *self.queue.lock().expect("mutex poisoned") = ReturnQueue::Replay(replay);
+        *self.set_location.lock().expect("mutex poisoned") = Some(Location::caller());
+    }
+
+    /// Set the spy return values from a JSON array, returned in order the same as [`set`](Self::set).
+    /// Enabled via the **serde** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self) -> Option<String>;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set_from_json(r#"["one", null]"#);
+    ///
+    /// assert_eq!(Some("one".to_string()), spy.foo());
+    /// assert_eq!(None, spy.foo());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the JSON is not an array of valid return values.
+    #[cfg(feature = "serde")]
+    #[track_caller]
+    pub fn set_from_json(&self, json: &str)
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let values: Vec<R> = serde_json::from_str(json)
+            .unwrap_or_else(|error| panic!("could not parse return values: {error}"));
+        self.set(values);
+    }
+
+    /// Set the spy return values from a JSON file containing an array, returned in order the same as
+    /// [`set`](Self::set). Enabled via the **serde** feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self) -> u8;
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("autospy_set_from_file_example.json");
+    /// std::fs::write(&path, "[1, 2]").unwrap();
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.set_from_file(&path);
+    ///
+    /// assert_eq!(1, spy.foo());
+    /// assert_eq!(2, spy.foo());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the file could not be read, or is not an array of valid return values.
+    #[cfg(feature = "serde")]
+    #[track_caller]
+    pub fn set_from_file(&self, path: impl AsRef<std::path::Path>)
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).unwrap_or_else(|error| {
+            panic!("could not read return values '{}': {error}", path.display())
+        });
+        let values: Vec<R> = serde_json::from_str(&json).unwrap_or_else(|error| {
+            panic!(
+                "could not parse return values '{}': {error}",
+                path.display()
+            )
+        });
+        self.set(values);
+    }
+
+    // only queued return values use the delays from set_with_delay, which are set alongside them
+    #[cfg(feature = "async")]
+    fn delay_of(&self, next: &NextReturn<R>) -> Option<Duration> {
+        let mut delays = self.delays.lock().expect("mutex poisoned");
+        if next.is_queued {
+            delays.next()
+        } else {
+            delays.all()
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn controller(&self) -> Option<ControllerSender<A, R>> {
+        self.controller.lock().expect("mutex poisoned").clone()
+    }
+
+    fn next(
+        &self,
+        arguments: &A,
+        fallback: Fallback,
+    ) -> Result<NextReturn<R>, CalledTooManyTimesError> {
+        if let Some(value) = self.next_from_rules(arguments) {
+            return Ok(NextReturn::other(value));
+        }
+        let queued = self.queue.lock().expect("mutex poisoned").next(arguments);
+        match (queued, fallback) {
+            (Ok(value), _) => Ok(NextReturn::queued(value)),
+            (Err(error), Fallback::Use) => self
+                .next_from_fallback(arguments)
+                .map(NextReturn::other)
+                .ok_or(error),
+            (Err(error), Fallback::Skip) => Err(error),
+        }
+    }
+
+    fn next_from_fallback(&self, arguments: &A) -> Option<R> {
+        self.fallback
+            .lock()
+            .expect("mutex poisoned")
+            .as_mut()
+            .map(|getter| getter(arguments))
+    }
+
+    fn next_from_rules(&self, arguments: &A) -> Option<R> {
+        self.rules
+            .lock()
+            .expect("mutex poisoned")
+            .iter_mut()
+            .filter(|rule| !rule.returns.is_exhausted())
+            .find(|rule| (rule.predicate)(arguments))
+            .and_then(|rule| rule.returns.next(arguments).ok())
+    }
+
+    fn add_rule(&self, predicate: Predicate<A>, returns: ReturnQueue<A, R>) {
+        self.rule_count.fetch_add(returns.len(), Ordering::Relaxed);
+        self.rules
+            .lock()
+            .expect("mutex poisoned")
+            .push(ReturnRule { predicate, returns });
+    }
+
+    fn clear(&self) {
+        *self.queue.lock().expect("mutex poisoned") = ReturnQueue::Finite(VecDeque::new());
+        *self.mutable_fn.lock().expect("mutex poisoned") = None;
+        #[cfg(feature = "async")]
+        {
+            *self.controller.lock().expect("mutex poisoned") = None;
+            *self.delays.lock().expect("mutex poisoned") = Delays::default();
+        }
+        self.rules.lock().expect("mutex poisoned").clear();
+        self.set_count.store(0, Ordering::Relaxed);
+        self.rule_count.store(0, Ordering::Relaxed);
+        *self.set_location.lock().expect("mutex poisoned") = None;
+    }
+
+    fn set_location(&self) -> Option<&'static Location<'static>> {
+        *self.set_location.lock().expect("mutex poisoned")
     }
 
-    fn next(&self, arguments: &A) -> Result<R, CalledTooManyTimesError> {
-        self.queue.lock().expect("mutex poisoned").next(arguments)
+    #[allow(clippy::significant_drop_tightening)]
+    fn with_unused<T>(&self, with: impl FnOnce(&[&R]) -> T) -> T {
+        let queue = self.queue.lock().expect("mutex poisoned");
+        let rules = self.rules.lock().expect("mutex poisoned");
+        let unused: Vec<_> = queue
+            .values()
+            .chain(rules.iter().flat_map(|rule| rule.returns.values()))
+            .collect();
+        with(&unused)
     }
 
     fn is_last_reference(&mut self) -> bool {
@@ -501,15 +1931,185 @@ impl<A, R> Returns<A, R> {
     }
 
     fn queue_len(&self) -> usize {
-        self.queue.lock().expect("mutex poisoned").len()
+        let rules_len: usize = self
+            .rules
+            .lock()
+            .expect("mutex poisoned")
+            .iter()
+            .map(|rule| rule.returns.len())
+            .sum();
+        self.queue.lock().expect("mutex poisoned").len() + rules_len
+    }
+}
+
+/// A conditional return rule, created using [`Returns::when`].
+///
+/// The rule only applies to calls whose [arguments](Arguments) match the predicate.
+/// Return values from [`then_return_once`](Self::then_return_once) and
+/// [`then_return_times`](Self::then_return_times) are treated the same as values from
+/// [`Returns::set`] and will panic if unused when the spy is dropped.
+#[must_use = "a rule does nothing until a return value is given"]
+pub struct When<'a, A, R, H: ?Sized = ()> {
+    returns: &'a Returns<A, R, H>,
+    predicate: Predicate<A>,
+}
+
+macro_rules! impl_set_fn_mut {
+    ($($out:ident),+) => {
+        impl<A, R, $($out: ?Sized),+> Returns<A, R, dyn FnMut(&A, $(&mut $out),+) -> R + Send> {
+            /// Set a return function for the spy which can write into the arguments marked with
+            /// `#[autospy(out)]`. It receives the captured arguments followed by the caller's mutable
+            /// references, and takes precedence over any other return values.
+            ///
+            /// # Examples
+            /// ```rust
+            /// #[autospy::autospy]
+            /// trait Source {
+            ///     fn read(&self, #[autospy(out)] buffer: &mut [u8]) -> usize;
+            /// }
+            ///
+            /// let spy = SourceSpy::default();
+            /// spy.read.returns.set_fn_mut(|_, buffer| {
+            ///     buffer[..2].copy_from_slice(b"hi");
+            ///     2
+            /// });
+            ///
+            /// let mut buffer = [0; 4];
+            /// assert_eq!(2, spy.read(&mut buffer));
+            /// assert_eq!(b"hi\0\0", &buffer);
+            ///
+            /// // the arguments are captured as they were passed in
+            /// assert_eq!([[0; 4].to_vec()], spy.read.arguments);
+            /// ```
+            #[allow(clippy::missing_panics_doc)]
+            pub fn set_fn_mut(
+                &self,
+                getter: impl FnMut(&A, $(&mut $out),+) -> R + Send + 'static,
+            ) {
+                *self.mutable_fn.lock().expect("mutex poisoned") = Some(Box::new(getter));
+            }
+        }
+    };
+}
+
+// the macro rejects functions with more out arguments than the arities implemented here
+impl_set_fn_mut!(O1);
+impl_set_fn_mut!(O1, O2);
+impl_set_fn_mut!(O1, O2, O3);
+impl_set_fn_mut!(O1, O2, O3, O4);
+
+impl<A, R, H: ?Sized> When<'_, A, R, H> {
+    /// Return the value every time the rule matches.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, id: u8) -> bool;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.when(|id| *id > 10).then_return(true);
+    ///
+    /// assert!(spy.foo(11));
+    /// assert!(spy.foo(12));
+    /// ```
+    pub fn then_return(self, value: R)
+    where
+        R: Clone,
+    {
+        self.returns.add_rule(
+            self.predicate,
+            ReturnQueue::Repeat {
+                value,
+                clone: R::clone,
+            },
+        );
+    }
+
+    /// Return the value the first time the rule matches.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, id: u8) -> String;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.when(|id| *id == 1).then_return_once("first".to_string());
+    /// spy.foo.returns.set(["fallback".to_string()]);
+    ///
+    /// assert_eq!("first", spy.foo(1));
+    /// assert_eq!("fallback", spy.foo(1));
+    /// ```
+    pub fn then_return_once(self, value: R) {
+        self.returns
+            .add_rule(self.predicate, std::iter::once(value).collect());
+    }
+
+    /// Return the value the first `times` times the rule matches.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, id: u8) -> u8;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.when(|id| *id == 1).then_return_times(2, 10);
+    ///
+    /// assert_eq!(10, spy.foo(1));
+    /// assert_eq!(10, spy.foo(1));
+    /// ```
+    pub fn then_return_times(self, times: usize, value: R)
+    where
+        R: Clone,
+    {
+        self.returns
+            .add_rule(self.predicate, std::iter::repeat_n(value, times).collect());
+    }
+
+    /// Return using the function every time the rule matches.
+    ///
+    /// # Examples
+    /// ```rust
+    /// #[autospy::autospy]
+    /// trait MyTrait {
+    ///     fn foo(&self, id: u8) -> u8;
+    /// }
+    ///
+    /// let spy = MyTraitSpy::default();
+    /// spy.foo.returns.when(|id| *id < 10).then_fn(|id| id * 2);
+    ///
+    /// assert_eq!(4, spy.foo(2));
+    /// assert_eq!(6, spy.foo(3));
+    /// ```
+    pub fn then_fn(self, getter: impl FnMut(&A) -> R + Send + 'static) {
+        self.returns
+            .add_rule(self.predicate, ReturnQueue::Infinite(Box::new(getter)));
     }
 }
 
 type GetReturn<A, R> = Box<dyn FnMut(&A) -> R + Send + 'static>;
 
+type Predicate<A> = Box<dyn Fn(&A) -> bool + Send + 'static>;
+
+struct ReturnRule<A, R> {
+    predicate: Predicate<A>,
+    returns: ReturnQueue<A, R>,
+}
+
 enum ReturnQueue<A, R> {
     Finite(VecDeque<R>),
     Infinite(GetReturn<A, R>),
+    Repeat {
+        value: R,
+        clone: fn(&R) -> R,
+    },
+    #[cfg(feature = "serde")]
+    Replay(Replay<A, R>),
 }
 
 impl<A, R> FromIterator<R> for ReturnQueue<A, R> {
@@ -523,15 +2123,106 @@ impl<A, R> ReturnQueue<A, R> {
         match self {
             Self::Finite(queue) => queue.pop_front().ok_or(CalledTooManyTimesError),
             Self::Infinite(getter) => Ok(getter(arguments)),
+            Self::Repeat { value, clone } => Ok(clone(value)),
+            #[cfg(feature = "serde")]
+            Self::Replay(replay) => replay.next(arguments).ok_or(CalledTooManyTimesError),
         }
     }
 
     fn len(&self) -> usize {
         match self {
             Self::Finite(queue) => queue.len(),
-            Self::Infinite(_) => 0,
+            Self::Infinite(_) | Self::Repeat { .. } => 0,
+            #[cfg(feature = "serde")]
+            Self::Replay(replay) => replay.len(),
+        }
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &R> + '_> {
+        match self {
+            Self::Finite(queue) => Box::new(queue.iter()),
+            Self::Infinite(_) | Self::Repeat { .. } => Box::new(std::iter::empty()),
+            #[cfg(feature = "serde")]
+            Self::Replay(replay) => Box::new(replay.values()),
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        match self {
+            Self::Finite(queue) => queue.is_empty(),
+            Self::Infinite(_) | Self::Repeat { .. } => false,
+            #[cfg(feature = "serde")]
+            Self::Replay(replay) => replay.len() == 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ExpectedCalls {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl ExpectedCalls {
+    const fn is_met_by(self, call_count: usize) -> bool {
+        match self {
+            Self::Exactly(times) => call_count == times,
+            Self::AtLeast(times) => call_count >= times,
+        }
+    }
+}
+
+impl std::fmt::Display for ExpectedCalls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exactly(times) => write!(f, "{times} time(s)"),
+            Self::AtLeast(times) => write!(f, "at least {times} time(s)"),
         }
     }
 }
 
 struct CalledTooManyTimesError;
+
+#[derive(Clone, Copy)]
+enum Fallback {
+    Use,
+    Skip,
+}
+
+struct NextReturn<R> {
+    value: R,
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    is_queued: bool,
+}
+
+impl<R> NextReturn<R> {
+    const fn queued(value: R) -> Self {
+        Self {
+            value,
+            is_queued: true,
+        }
+    }
+
+    const fn other(value: R) -> Self {
+        Self {
+            value,
+            is_queued: false,
+        }
+    }
+}
+
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `Default`, so it cannot be returned by a function of a `nice` spy",
+    label = "the return type of this function does not implement `Default`",
+    note = "declare its fallback return value with `#[autospy(default_return = \"...\")]`"
+)]
+pub trait NiceReturn {
+    fn nice_return() -> Self;
+}
+
+impl<T: Default> NiceReturn for T {
+    fn nice_return() -> Self {
+        Self::default()
+    }
+}
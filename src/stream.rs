@@ -0,0 +1,22 @@
+use crate::Arguments;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct ArgumentsStream<A> {
+    arguments: Arguments<A>,
+}
+
+impl<A> ArgumentsStream<A> {
+    pub const fn new(arguments: Arguments<A>) -> Self {
+        Self { arguments }
+    }
+}
+
+impl<A> Stream for ArgumentsStream<A> {
+    type Item = A;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<A>> {
+        self.arguments.poll_next_call(context).map(Some)
+    }
+}
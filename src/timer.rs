@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::sync::{Condvar, Mutex, Once};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// Wakes sleeping tasks from a single shared thread, so sleeping does not need an async runtime
+/// or a thread per sleep.
+struct Timer {
+    sleepers: Mutex<Sleepers>,
+    condvar: Condvar,
+}
+
+struct Sleepers {
+    wakers: BTreeMap<(Instant, u64), Waker>,
+    next_id: u64,
+}
+
+static TIMER: Timer = Timer {
+    sleepers: Mutex::new(Sleepers {
+        wakers: BTreeMap::new(),
+        next_id: 0,
+    }),
+    condvar: Condvar::new(),
+};
+
+static TIMER_THREAD: Once = Once::new();
+
+impl Timer {
+    fn run(&self) {
+        loop {
+            let mut sleepers = self.sleepers.lock().expect("mutex poisoned");
+            let expired = sleepers.expire(Instant::now());
+            if !expired.is_empty() {
+                drop(sleepers);
+                expired.into_iter().for_each(Waker::wake);
+                continue;
+            }
+            match sleepers.wakers.keys().next() {
+                Some(&(deadline, _)) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    drop(self.condvar.wait_timeout(sleepers, timeout));
+                }
+                None => drop(self.condvar.wait(sleepers)),
+            }
+        }
+    }
+}
+
+impl Sleepers {
+    fn expire(&mut self, now: Instant) -> Vec<Waker> {
+        let mut expired = Vec::new();
+        while let Some(entry) = self.wakers.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            expired.push(entry.remove());
+        }
+        expired
+    }
+}
+
+/// Completes once its duration has passed, and stops waiting when dropped.
+pub struct Sleep {
+    deadline: Option<Instant>,
+    key: Option<(Instant, u64)>,
+}
+
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: Instant::now().checked_add(duration),
+        key: None,
+    }
+}
+
+impl Sleep {
+    fn register(&mut self, deadline: Instant, waker: &Waker) {
+        let mut sleepers = TIMER.sleepers.lock().expect("mutex poisoned");
+        let key = *self.key.get_or_insert_with(|| {
+            sleepers.next_id += 1;
+            (deadline, sleepers.next_id)
+        });
+        sleepers.wakers.insert(key, waker.clone());
+        drop(sleepers);
+
+        TIMER.condvar.notify_one();
+        TIMER_THREAD.call_once(|| {
+            std::thread::Builder::new()
+                .name("autospy-timer".to_string())
+                .spawn(|| TIMER.run())
+                .expect("failed to spawn the autospy timer thread");
+        });
+    }
+
+    fn deregister(&mut self) {
+        if let Some(key) = self.key.take() {
+            TIMER
+                .sleepers
+                .lock()
+                .expect("mutex poisoned")
+                .wakers
+                .remove(&key);
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        // a deadline too far away to represent never passes
+        let Some(deadline) = this.deadline else {
+            return Poll::Pending;
+        };
+        if Instant::now() >= deadline {
+            this.deregister();
+            return Poll::Ready(());
+        }
+        this.register(deadline, context.waker());
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.deregister();
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "async")]
+
+use futures::StreamExt;
+use std::time::Duration;
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    async fn function(&self, argument: u8);
+}
+
+fn use_trait<T: MyTrait>(trait_object: T, arguments: &'static [u8]) {
+    tokio::task::spawn(async move {
+        for argument in arguments {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            trait_object.function(*argument).await;
+        }
+    });
+}
+
+#[tokio::test]
+async fn stream_yields_captured_arguments_in_order() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &[1, 2, 3]);
+
+    assert_eq!(
+        vec![1, 2, 3],
+        spy.function
+            .arguments
+            .stream()
+            .take(3)
+            .collect::<Vec<_>>()
+            .await
+    );
+    assert!(spy.function.arguments.is_empty());
+}
+
+#[tokio::test]
+async fn stream_yields_already_captured_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function(1).await;
+
+    let mut stream = spy.function.arguments.stream();
+
+    assert_eq!(Some(1), stream.next().await);
+}
+
+#[tokio::test]
+async fn stream_waits_for_further_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    let mut stream = spy.function.arguments.stream();
+
+    assert!(
+        tokio::time::timeout(Duration::from_millis(10), stream.next())
+            .await
+            .is_err()
+    );
+
+    spy.function(1).await;
+
+    assert_eq!(Some(1), stream.next().await);
+}
+
+#[tokio::test]
+async fn stream_can_be_used_with_combinators() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &[1, 2, 3, 4]);
+
+    assert_eq!(
+        vec![2, 4],
+        spy.function
+            .arguments
+            .stream()
+            .filter(|argument| std::future::ready(argument % 2 == 0))
+            .take(2)
+            .collect::<Vec<_>>()
+            .await
+    );
+}
@@ -0,0 +1,185 @@
+#[autospy::autospy]
+trait MyTrait {
+    fn function(&self, argument: u8);
+    fn multiple(&self, id: u8, name: &str);
+}
+
+fn use_trait<T: MyTrait>(trait_object: &T, arguments: &[u8]) {
+    for argument in arguments {
+        trait_object.function(*argument);
+    }
+}
+
+#[test]
+fn assert_called_passes_when_called() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2]);
+
+    autospy::assert_called!(spy.function);
+    autospy::assert_called!(spy.function, times = 2);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called, but was called 0 time(s)"
+)]
+fn assert_called_panics_when_not_called() {
+    let spy = MyTraitSpy::default();
+
+    autospy::assert_called!(spy.function);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called 1 time(s), but was called 2 time(s)
+   [0] 1
+   [1] 2"
+)]
+fn assert_called_times_panics_listing_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2]);
+
+    autospy::assert_called!(spy.function, times = 1);
+}
+
+#[test]
+fn assert_not_called_passes_when_not_called() {
+    let spy = MyTraitSpy::default();
+
+    autospy::assert_not_called!(spy.function);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected not to be called, but was called 1 time(s)
+   [0] 7"
+)]
+fn assert_not_called_panics_when_called() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[7]);
+
+    autospy::assert_not_called!(spy.function);
+}
+
+#[test]
+fn assert_called_with_compares_last_call() {
+    let spy = MyTraitSpy::default();
+    spy.multiple.returns.set([(), ()]);
+
+    spy.multiple(1, "a");
+    spy.multiple(2, "b");
+
+    autospy::assert_called_with!(spy.multiple, (2, "b".to_string()));
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was not last called with the expected arguments (- expected, + actual):
+   [0] 1
+  -[1] 3
+  +[1] 2"
+)]
+fn assert_called_with_panics_with_diff_of_last_call() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2]);
+
+    autospy::assert_called_with!(spy.function, 3);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called with 3, but was not called"
+)]
+fn assert_called_with_panics_when_not_called() {
+    let spy = MyTraitSpy::default();
+
+    autospy::assert_called_with!(spy.function, 3);
+}
+
+#[test]
+fn assert_called_with_calls_accepts_arrays_vecs_and_slices() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2]);
+
+    autospy::assert_called_with!(spy.function, calls = [1, 2]);
+    autospy::assert_called_with!(spy.function, calls = vec![1, 2]);
+    autospy::assert_called_with!(spy.function, calls = [1, 2].as_slice());
+    autospy::assert_called_with!(spy.function, calls = &[1, 2]);
+}
+
+#[test]
+fn assert_called_with_calls_compares_borrowed_expected_values() {
+    let spy = MyTraitSpy::default();
+    spy.multiple.returns.set([()]);
+
+    spy.multiple(1, "a");
+
+    autospy::assert_called_with!(spy.multiple, calls = [(1, "a".to_string())]);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was not called with the expected arguments (- expected, + actual):
+   [0] 1
+  -[1] 5
+  +[1] 2
+  +[2] 3"
+)]
+fn assert_called_with_calls_panics_with_per_call_diff() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2, 3]);
+
+    autospy::assert_called_with!(spy.function, calls = [1, 5]);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was not called with the expected arguments (- expected, + actual):
+   [0] 1
+  -[1] 2"
+)]
+fn assert_called_with_calls_shows_missing_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1]);
+
+    autospy::assert_called_with!(spy.function, calls = [1, 2]);
+}
+
+#[test]
+fn assert_any_call_passes_when_any_call_matches() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2, 3]);
+
+    autospy::assert_any_call!(spy.function, 2);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was never called with 4, but was called 2 time(s)
+   [0] 1
+   [1] 2"
+)]
+fn assert_any_call_panics_listing_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(&spy, &[1, 2]);
+
+    autospy::assert_any_call!(spy.function, 4);
+}
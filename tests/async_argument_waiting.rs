@@ -0,0 +1,181 @@
+#![cfg(feature = "async")]
+
+use std::time::Duration;
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    async fn function(&self, topic: String);
+}
+
+fn use_trait<T: MyTrait>(trait_object: T, topics: &'static [&'static str]) {
+    tokio::task::spawn(async move {
+        for topic in topics {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            trait_object.function((*topic).to_string()).await;
+        }
+    });
+}
+
+#[tokio::test]
+async fn recv_n_waits_for_number_of_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &["users", "orders", "payments"]);
+
+    assert_eq!(
+        vec!["users".to_string(), "orders".to_string()],
+        spy.function.arguments.recv_n(2).await
+    );
+    assert_eq!(
+        vec!["payments".to_string()],
+        spy.function.arguments.recv_n(1).await
+    );
+}
+
+#[tokio::test]
+async fn recv_until_waits_for_matching_call() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &["users", "orders"]);
+
+    assert_eq!(
+        "orders",
+        spy.function
+            .arguments
+            .recv_until(|topic| topic == "orders")
+            .await
+    );
+    assert_eq!(["users".to_string()], spy.function.arguments);
+}
+
+#[tokio::test]
+async fn next_call_takes_one_call_at_a_time() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &["users", "orders"]);
+
+    assert_eq!("users", spy.function.arguments.next_call().await);
+    assert_eq!("orders", spy.function.arguments.next_call().await);
+    assert!(spy.function.arguments.is_empty());
+}
+
+#[tokio::test]
+async fn already_captured_arguments_are_returned_immediately() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("users".to_string()).await;
+
+    assert_eq!(
+        "users",
+        spy.function
+            .arguments
+            .next_call_timeout(Duration::from_millis(10))
+            .await
+    );
+}
+
+#[tokio::test]
+async fn timeout_variants_return_when_called_in_time() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &["users", "orders"]);
+
+    assert_eq!(
+        vec!["users".to_string(), "orders".to_string()],
+        spy.function
+            .arguments
+            .recv_n_timeout(2, Duration::from_secs(5))
+            .await
+    );
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for 2 call(s), captured so far: [\"users\"]"
+)]
+async fn recv_n_timeout_panics_with_captured_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait(spy.clone(), &["users"]);
+
+    let _ = spy
+        .function
+        .arguments
+        .recv_n_timeout(2, Duration::from_millis(50))
+        .await;
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for a matching call, captured so far: [\"users\"]"
+)]
+async fn recv_until_timeout_panics_with_captured_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("users".to_string()).await;
+
+    let _ = spy
+        .function
+        .arguments
+        .recv_until_timeout(|topic| topic == "orders", Duration::from_millis(50))
+        .await;
+}
+
+#[tokio::test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for a call, captured so far: []"
+)]
+async fn next_call_timeout_panics_when_never_called() {
+    let spy = MyTraitSpy::default();
+
+    let _ = spy
+        .function
+        .arguments
+        .next_call_timeout(Duration::from_millis(50))
+        .await;
+}
+
+#[test]
+fn timeout_variants_work_without_a_tokio_runtime() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    std::thread::spawn({
+        let spy = spy.clone();
+        move || {
+            std::thread::sleep(Duration::from_millis(10));
+            pollster::block_on(spy.function("users".to_string()));
+        }
+    });
+
+    assert_eq!(
+        "users",
+        pollster::block_on(
+            spy.function
+                .arguments
+                .next_call_timeout(Duration::from_secs(5))
+        )
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for a call, captured so far: []"
+)]
+fn timeout_variants_time_out_without_a_tokio_runtime() {
+    let spy = MyTraitSpy::default();
+
+    let _ = pollster::block_on(
+        spy.function
+            .arguments
+            .next_call_timeout(Duration::from_millis(50)),
+    );
+}
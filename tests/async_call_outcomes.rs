@@ -0,0 +1,110 @@
+#![cfg(feature = "async")]
+
+use autospy::CallOutcome;
+use std::time::Duration;
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    async fn function(&self, argument: u8) -> u8;
+}
+
+async fn use_trait_with_timeout<T: MyTrait>(trait_object: &T) -> Option<u8> {
+    tokio::time::timeout(Duration::from_millis(10), trait_object.function(1))
+        .await
+        .ok()
+}
+
+#[tokio::test]
+async fn completed_calls_are_recorded() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1, 2]);
+
+    spy.function(1).await;
+    spy.function(2).await;
+
+    assert_eq!(
+        [CallOutcome::Completed, CallOutcome::Completed],
+        spy.function.call_outcomes().as_slice()
+    );
+    assert_eq!(0, spy.function.in_flight());
+    assert_eq!(1, spy.function.max_concurrent_calls());
+}
+
+#[tokio::test]
+async fn calls_dropped_by_timeout_are_cancelled() {
+    let spy = MyTraitSpy::default();
+    let _controller = spy.function.returns.controlled();
+
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+
+    assert_eq!(
+        [CallOutcome::Cancelled],
+        spy.function.call_outcomes().as_slice()
+    );
+    assert_eq!(0, spy.function.in_flight());
+}
+
+#[tokio::test]
+async fn calls_dropped_by_select_are_cancelled() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|argument| *argument);
+    spy.function.returns.delay_all(Duration::from_secs(1));
+
+    tokio::select! {
+        _ = spy.function(1) => panic!("spy function should not complete first"),
+        () = tokio::time::sleep(Duration::from_millis(10)) => {}
+    }
+
+    assert_eq!(
+        [CallOutcome::Cancelled],
+        spy.function.call_outcomes().as_slice()
+    );
+}
+
+#[tokio::test]
+async fn concurrent_calls_are_tracked() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    let tasks: Vec<_> = (0..3)
+        .map(|argument| {
+            let spy = spy.clone();
+            tokio::task::spawn(async move { spy.function(argument).await })
+        })
+        .collect();
+
+    let mut calls = Vec::new();
+    for _ in 0..3 {
+        calls.push(controller.next_call().await);
+    }
+
+    assert_eq!(3, spy.function.in_flight());
+    assert_eq!(
+        [CallOutcome::InFlight; 3],
+        spy.function.call_outcomes().as_slice()
+    );
+
+    for call in calls {
+        let value = *call.arguments();
+        call.respond(value);
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    assert_eq!(0, spy.function.in_flight());
+    assert_eq!(3, spy.function.max_concurrent_calls());
+}
+
+#[tokio::test]
+async fn reset_clears_call_outcomes() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+
+    spy.function(1).await;
+    spy.autospy_reset();
+
+    assert!(spy.function.call_outcomes().is_empty());
+    assert_eq!(0, spy.function.max_concurrent_calls());
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "async")]
+
 #[autospy::autospy]
 #[async_trait::async_trait]
 trait MyTrait: Send + 'static {
@@ -26,3 +28,41 @@ async fn async_function_argument_captured_and_can_be_taken_with_timeout() {
         spy.function.arguments.recv().await
     );
 }
+
+#[tokio::test]
+async fn recv_takes_all_calls_and_then_waits_for_new_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("first".to_string()).await;
+    spy.function("second".to_string()).await;
+
+    assert_eq!(
+        vec!["first".to_string(), "second".to_string()],
+        spy.function.arguments.recv().await
+    );
+    assert!(
+        tokio::time::timeout(
+            std::time::Duration::from_millis(10),
+            spy.function.arguments.recv()
+        )
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn recv_waits_for_new_calls_after_arguments_taken() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("taken".to_string()).await;
+    let _ = spy.function.arguments.take();
+
+    use_trait(spy.clone());
+
+    assert_eq!(
+        vec!["argument".to_string()],
+        spy.function.arguments.recv().await
+    );
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+#[autospy::autospy]
+trait MyTrait: Send + Sync + 'static {
+    fn function(&self, topic: String);
+}
+
+fn use_trait_in_thread<T: MyTrait>(trait_object: T, topics: &'static [&'static str]) {
+    std::thread::spawn(move || {
+        for topic in topics {
+            std::thread::sleep(Duration::from_millis(10));
+            trait_object.function((*topic).to_string());
+        }
+    });
+}
+
+#[test]
+fn wait_for_blocks_until_number_of_calls() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait_in_thread(spy.clone(), &["users", "orders", "payments"]);
+
+    assert_eq!(
+        vec!["users".to_string(), "orders".to_string()],
+        spy.function.arguments.wait_for(2, Duration::from_secs(5))
+    );
+    assert_eq!(
+        vec!["payments".to_string()],
+        spy.function.arguments.wait_for(1, Duration::from_secs(5))
+    );
+}
+
+#[test]
+fn wait_until_blocks_until_matching_call() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait_in_thread(spy.clone(), &["users", "orders"]);
+
+    assert_eq!(
+        "orders",
+        spy.function
+            .arguments
+            .wait_until(|topic| topic == "orders", Duration::from_secs(5))
+    );
+    assert_eq!(["users".to_string()], spy.function.arguments);
+}
+
+#[test]
+fn already_captured_arguments_are_returned_immediately() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("users".to_string());
+
+    assert_eq!(
+        vec!["users".to_string()],
+        spy.function.arguments.wait_for(1, Duration::ZERO)
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for 2 call(s), captured so far: [\"users\"]"
+)]
+fn wait_for_panics_with_captured_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    use_trait_in_thread(spy.clone(), &["users"]);
+
+    let _ = spy
+        .function
+        .arguments
+        .wait_for(2, Duration::from_millis(50));
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' timed out after 50ms waiting for a matching call, captured so far: [\"users\"]"
+)]
+fn wait_until_panics_with_captured_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function("users".to_string());
+
+    let _ = spy
+        .function
+        .arguments
+        .wait_until(|topic| topic == "orders", Duration::from_millis(50));
+}
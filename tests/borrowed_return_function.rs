@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[autospy::autospy(calls)]
+trait MyTrait {
+    #[autospy(borrowed)]
+    fn trim<'a>(&self, text: &'a str) -> &'a str;
+    #[autospy(borrowed)]
+    fn subscribe(&self, #[autospy(ignore)] callback: &dyn Fn(u8), value: u8);
+    #[autospy(borrowed)]
+    fn push(&self, #[autospy(ignore)] values: &mut Vec<u8>, value: u8);
+}
+
+#[autospy::autospy]
+trait MyGenericTrait {
+    #[autospy(borrowed)]
+    fn apply(&self, function: impl Fn(u8) -> u8 + 'static, value: u8) -> u8;
+}
+
+#[test]
+fn return_function_can_return_borrowed_from_argument() {
+    let spy = MyTraitSpy::default();
+    spy.trim.returns.set_fn_borrowed(|text: &str| text.trim());
+
+    let text = String::from("  hello  ");
+
+    assert_eq!("hello", spy.trim(&text));
+    assert_eq!(["  hello  "], spy.trim.arguments);
+}
+
+#[test]
+fn return_function_can_invoke_ignored_callback() {
+    let spy = MyTraitSpy::default();
+    spy.subscribe
+        .returns
+        .set_fn_borrowed(|callback: &dyn Fn(u8), value: &u8| {
+            callback(*value);
+        });
+
+    let received = AtomicU8::new(0);
+    spy.subscribe(&|value| received.store(value, Ordering::Relaxed), 5);
+
+    assert_eq!(5, received.load(Ordering::Relaxed));
+    assert_eq!([5], spy.subscribe.arguments);
+    assert_eq!([MyTraitSpyCall::Subscribe(5)], spy.autospy_calls());
+}
+
+#[test]
+fn return_function_receives_generic_arguments_as_dyn() {
+    let spy = MyGenericTraitSpy::default();
+    spy.apply
+        .returns
+        .set_fn_borrowed(|function, value| function(*value));
+
+    assert_eq!(4, spy.apply(|value| value * 2, 2));
+    assert_eq!(2, spy.apply.arguments.take()[0].1);
+}
+
+#[test]
+fn return_function_can_write_into_mutable_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.push
+        .returns
+        .set_fn_borrowed(|values: &mut Vec<u8>, value: &u8| {
+            values.push(*value);
+        });
+
+    let mut values = vec![1];
+    spy.push(&mut values, 2);
+
+    assert_eq!(vec![1, 2], values);
+}
+
+#[test]
+fn other_return_values_are_used_without_return_function() {
+    let spy = MyTraitSpy::default();
+    spy.trim.returns.set(["set"]);
+
+    assert_eq!("set", spy.trim("  hello  "));
+}
@@ -0,0 +1,96 @@
+#[autospy::autospy]
+trait MyTrait {
+    fn function(&self, id: u8) -> u8;
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T, times: u8) {
+    for id in 0..times {
+        trait_object.function(id);
+    }
+}
+
+#[test]
+fn expected_calls_met_does_not_panic_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_calls(2);
+
+    use_test_trait(&spy, 2);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called 2 time(s), but was called 1 time(s)"
+)]
+fn too_few_calls_panics_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_calls(2);
+
+    use_test_trait(&spy, 1);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called 2 time(s), but was called 3 time(s)"
+)]
+fn too_many_calls_panics_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_calls(2);
+
+    use_test_trait(&spy, 3);
+}
+
+#[test]
+fn expected_at_least_met_does_not_panic_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_at_least(1);
+
+    use_test_trait(&spy, 3);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called at least 1 time(s), but was called 0 time(s)"
+)]
+fn too_few_calls_for_at_least_panics_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_at_least(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' was expected to be called 0 time(s), but was called 1 time(s)"
+)]
+fn expected_never_panics_when_dropped_if_called() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_never();
+
+    use_test_trait(&spy, 1);
+}
+
+#[test]
+fn expectations_are_only_verified_when_last_clone_is_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|id| *id);
+    spy.function.expect_calls(1);
+
+    drop(spy.clone());
+
+    use_test_trait(&spy, 1);
+}
+
+#[test]
+fn taking_arguments_does_not_affect_call_count() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1, 2]);
+    spy.function.expect_calls(2);
+
+    use_test_trait(&spy, 2);
+
+    assert_eq!(vec![0, 1], spy.function.arguments.take());
+}
@@ -0,0 +1,99 @@
+use std::time::Instant;
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    fn function(&self, argument: u8);
+    fn other_function(&self, #[autospy(out)] output: &mut u8);
+    async fn async_function(&self);
+}
+
+fn use_trait<T: MyTrait>(trait_object: &T) -> u32 {
+    let line = line!() + 1;
+    trait_object.function(1);
+    line
+}
+
+#[test]
+fn call_location_is_caller_of_trait_function() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([()]);
+
+    let line = use_trait(&spy);
+
+    let calls = spy.function.arguments.calls();
+    assert_eq!(1, calls.len());
+    assert_eq!(line, calls[0].location.line());
+    assert_eq!(file!(), calls[0].location.file());
+}
+
+#[test]
+fn call_location_of_out_argument_function_is_caller() {
+    let spy = MyTraitSpy::default();
+    spy.other_function
+        .returns
+        .set_fn_mut(|_, output| *output = 1);
+
+    let mut output = 0;
+    let line = line!() + 1;
+    spy.other_function(&mut output);
+
+    assert_eq!(
+        line,
+        spy.other_function.arguments.calls()[0].location.line()
+    );
+}
+
+#[test]
+fn call_thread_and_timestamp_are_recorded() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    let before = Instant::now();
+    std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn({
+            let spy = spy.clone();
+            move || spy.function(1)
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    let call = &spy.function.arguments.calls()[0];
+    assert_eq!(Some("worker"), call.thread.name());
+    assert!(call.timestamp >= before);
+}
+
+#[test]
+fn records_are_kept_when_arguments_are_taken() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function(1);
+    spy.function(2);
+
+    assert_eq!(vec![1, 2], spy.function.arguments.take());
+    assert_eq!(2, spy.function.arguments.calls().len());
+}
+
+#[test]
+fn records_are_cleared_on_reset() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+
+    spy.function(1);
+    spy.autospy_reset();
+
+    assert!(spy.function.arguments.calls().is_empty());
+}
+
+#[tokio::test]
+async fn async_calls_are_recorded() {
+    let spy = MyTraitSpy::default();
+    spy.async_function.returns.set([()]);
+
+    spy.async_function().await;
+
+    assert_eq!(1, spy.async_function.arguments.calls().len());
+}
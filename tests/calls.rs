@@ -0,0 +1,155 @@
+#[autospy::autospy(calls)]
+trait MyTrait {
+    fn open(&self);
+    fn write(&self, id: u8, bytes: &[u8]) -> usize;
+    fn close(&self);
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T) {
+    trait_object.open();
+    trait_object.write(1, &[1, 2]);
+    trait_object.write(2, &[3]);
+    trait_object.close();
+}
+
+#[test]
+fn calls_are_recorded_in_order() {
+    let spy = MyTraitSpy::default();
+    spy.open.returns.set([()]);
+    spy.write.returns.set([2, 1]);
+    spy.close.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!(
+        [
+            MyTraitSpyCall::Open,
+            MyTraitSpyCall::Write((1, vec![1, 2])),
+            MyTraitSpyCall::Write((2, vec![3])),
+            MyTraitSpyCall::Close,
+        ],
+        spy.autospy_calls()
+    );
+}
+
+#[test]
+fn arguments_are_still_captured_per_function() {
+    let spy = MyTraitSpy::default();
+    spy.open.returns.set([()]);
+    spy.write.returns.set([2, 1]);
+    spy.close.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!([(1, vec![1, 2]), (2, vec![3])], spy.write.arguments);
+}
+
+#[test]
+fn calls_are_shared_between_clones() {
+    let spy = MyTraitSpy::default();
+    spy.open.returns.set([()]);
+
+    let clone = spy.clone();
+    clone.open();
+    drop(clone);
+
+    assert_eq!([MyTraitSpyCall::Open], spy.autospy_calls());
+}
+
+#[test]
+fn take_clears_the_recorded_calls() {
+    let spy = MyTraitSpy::default();
+    spy.open.returns.set([()]);
+    spy.close.returns.set([()]);
+
+    spy.open();
+    spy.close();
+
+    assert_eq!(
+        vec![MyTraitSpyCall::Open, MyTraitSpyCall::Close],
+        spy.autospy_calls().take()
+    );
+    assert!(spy.autospy_calls().take().is_empty());
+}
+
+#[autospy::autospy(calls)]
+trait MySupertraitTrait: Supertrait {
+    fn function(&self);
+    autospy::supertrait! {
+        trait Supertrait {
+            fn super_function(&self, value: u8);
+        }
+    }
+}
+
+trait Supertrait {
+    fn super_function(&self, value: u8);
+}
+
+#[test]
+fn supertrait_calls_are_recorded() {
+    let spy = MySupertraitTraitSpy::default();
+    spy.function.returns.set([()]);
+    spy.super_function.returns.set([()]);
+
+    spy.super_function(5);
+    spy.function();
+
+    assert_eq!(
+        [
+            MySupertraitTraitSpyCall::SuperFunction(5),
+            MySupertraitTraitSpyCall::Function,
+        ],
+        spy.autospy_calls()
+    );
+}
+
+#[autospy::autospy(calls)]
+trait MyGenericTrait<T: Copy, U> {
+    fn function(&self, value: T) -> U;
+}
+
+#[test]
+fn generic_trait_calls_are_recorded() {
+    let spy = MyGenericTraitSpy::<u8, String>::default();
+    spy.function.returns.set(["hello".to_string()]);
+
+    assert_eq!("hello", spy.function(3));
+    assert_eq!([MyGenericTraitSpyCall::Function(3)], spy.autospy_calls());
+}
+
+#[derive(Clone)]
+struct Payload(u8);
+
+#[autospy::autospy(calls)]
+trait MyPayloadTrait {
+    fn send(&self, topic: &str, payload: Payload);
+    fn calls(&self) -> u8;
+}
+
+#[test]
+fn calls_are_recorded_when_arguments_are_not_debug_or_partial_eq() {
+    let spy = MyPayloadTraitSpy::default();
+    spy.send.returns.set([()]);
+
+    spy.send("topic", Payload(1));
+
+    assert_eq!(
+        "send: called 1 time(s), not Debug\ncalls: not called\ncalls: 1 call(s), not Debug",
+        spy.autospy_render_interactions()
+    );
+    let calls = spy.autospy_calls().take();
+    let [MyPayloadTraitSpyCall::Send((topic, Payload(payload)))] = calls.as_slice() else {
+        panic!("expected a single send call");
+    };
+    assert_eq!(("topic", 1), (topic.as_str(), *payload));
+}
+
+#[test]
+fn trait_function_named_calls_is_not_shadowed() {
+    let spy = MyPayloadTraitSpy::default();
+    spy.calls.returns.set([3]);
+
+    assert_eq!(3, spy.calls());
+    assert_eq!(1, spy.autospy_calls().get().len());
+}
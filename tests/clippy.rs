@@ -6,3 +6,123 @@
 trait MyTrait {
     fn function(&self);
 }
+
+#[autospy::autospy(calls)]
+#[allow(dead_code)]
+trait MyCallsTrait {
+    fn function(&self, value: u8);
+    fn other_function(&self);
+}
+
+#[autospy::autospy(nice)]
+#[allow(dead_code)]
+trait MyNiceTrait {
+    fn function(&self) -> u8;
+    #[autospy(default_return = "Ok(())")]
+    fn other_function(&self) -> Result<(), String>;
+}
+
+#[autospy::autospy(named_arguments)]
+#[allow(dead_code)]
+trait MyNamedArgumentsTrait {
+    fn function(&self, name: &str, value: u8);
+    fn not_debug_function(&self, name: &str, value: NotDebug);
+}
+
+#[autospy::autospy(calls)]
+#[allow(dead_code)]
+trait MyOutArgumentsTrait {
+    fn function(&self, value: u8, #[autospy(out)] output: &mut Vec<u8>) -> bool;
+    fn written_function(
+        &self,
+        value: u8,
+        #[autospy(out)]
+        #[autospy(capture_written)]
+        output: &mut Vec<u8>,
+    ) -> bool;
+}
+
+#[autospy::autospy(calls)]
+#[allow(dead_code)]
+trait MyBorrowedTrait {
+    #[autospy(borrowed)]
+    fn function<'a>(&self, text: &'a str, #[autospy(ignore)] callback: &dyn Fn(u8)) -> &'a str;
+}
+
+#[autospy::autospy]
+#[allow(dead_code)]
+trait MyNestedReferencesTrait {
+    fn function(&self, name: Option<&str>, tags: &[&str], pair: (&str, u8));
+}
+
+#[autospy::autospy(calls)]
+#[async_trait::async_trait]
+#[allow(dead_code)]
+trait MyAsyncTrait: Send + Sync + 'static {
+    async fn function(&self, value: u8) -> u8;
+}
+
+#[autospy::autospy(wrapping)]
+#[async_trait::async_trait]
+#[allow(dead_code)]
+trait MyWrappingTrait<T: Clone + Send + Sync + 'static>: Send + Sync {
+    #[autospy(String)]
+    type Item;
+    fn function(&self, value: T, name: &str, #[autospy(out)] output: &mut u8) -> Self::Item;
+    async fn async_function(&self, #[autospy(into = "u32")] value: u8) -> u8;
+    fn mutable_function(&mut self, value: u8);
+}
+
+#[derive(Clone)]
+struct NotDebug;
+
+#[autospy::autospy(calls)]
+#[allow(dead_code)]
+trait MyNotDebugCallsTrait {
+    fn function(&self, value: NotDebug, name: &str);
+    fn calls(&self) -> u8;
+}
+
+#[autospy::autospy(calls)]
+#[allow(dead_code)]
+trait MyNoSpyFunctionsCallsTrait {
+    #[autospy(use_default)]
+    fn function(&self) -> u8 {
+        1
+    }
+}
+
+#[autospy::autospy(calls)]
+#[async_trait::async_trait]
+#[allow(dead_code)]
+trait MyRecordDefaultTrait: Send + Sync + 'static {
+    fn function(&self, value: u8) -> u8;
+    #[autospy(record_default)]
+    fn recorded(&self, value: u8, name: String) -> u8 {
+        self.function(value) + u8::try_from(name.len()).unwrap_or_default()
+    }
+    #[autospy(record_default)]
+    fn recorded_mutable(&mut self) {}
+    #[autospy(record_default)]
+    async fn recorded_async(&self, #[autospy(ignore)] value: u8) -> u8 {
+        self.function(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[autospy::autospy(recording)]
+#[async_trait::async_trait]
+#[allow(dead_code)]
+trait MyRecordingTrait: Send + Sync {
+    fn function(&self, value: u8, name: &str) -> Option<String>;
+    async fn async_function(&self) -> u8;
+    fn mutable_function(&mut self, value: u8);
+}
+
+#[cfg(feature = "serde")]
+#[autospy::autospy(serialize)]
+#[allow(dead_code)]
+trait MySerializeTrait<T: Clone> {
+    fn function(&self, value: T, name: &str) -> Option<String>;
+    fn r#type(&self);
+}
@@ -0,0 +1,143 @@
+#[autospy::autospy]
+trait MyTrait {
+    fn function(&self, id: u8) -> String;
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T, id: u8) -> String {
+    trait_object.function(id)
+}
+
+#[test]
+fn rule_returns_value_when_arguments_match() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return("three".to_string());
+
+    assert_eq!("three", use_test_trait(&spy, 3));
+    assert_eq!("three", use_test_trait(&spy, 3));
+    assert_eq!([3, 3], spy.function.arguments);
+}
+
+#[test]
+fn set_values_are_used_when_no_rule_matches() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return("three".to_string());
+    spy.function.returns.set(["other".to_string()]);
+
+    assert_eq!("other", use_test_trait(&spy, 1));
+    assert_eq!("three", use_test_trait(&spy, 3));
+}
+
+#[test]
+fn set_fn_is_used_when_no_rule_matches() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return("three".to_string());
+    spy.function.returns.set_fn(u8::to_string);
+
+    assert_eq!("1", use_test_trait(&spy, 1));
+    assert_eq!("three", use_test_trait(&spy, 3));
+    assert_eq!("2", use_test_trait(&spy, 2));
+}
+
+#[test]
+fn rules_are_checked_in_the_order_they_were_added() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return_once("first".to_string());
+    spy.function
+        .returns
+        .when(|id| *id > 2)
+        .then_return("second".to_string());
+
+    assert_eq!("first", use_test_trait(&spy, 3));
+    assert_eq!("second", use_test_trait(&spy, 3));
+    assert_eq!("second", use_test_trait(&spy, 4));
+}
+
+#[test]
+fn rule_with_times_is_used_that_many_times() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return_times(2, "three".to_string());
+    spy.function.returns.set(["other".to_string()]);
+
+    assert_eq!("three", use_test_trait(&spy, 3));
+    assert_eq!("three", use_test_trait(&spy, 3));
+    assert_eq!("other", use_test_trait(&spy, 3));
+}
+
+#[test]
+fn rule_with_fn_uses_the_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id % 2 == 0)
+        .then_fn(|id| format!("even {id}"));
+    spy.function.returns.set(["odd".to_string()]);
+
+    assert_eq!("even 2", use_test_trait(&spy, 2));
+    assert_eq!("odd", use_test_trait(&spy, 3));
+    assert_eq!("even 4", use_test_trait(&spy, 4));
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' had 0 return values set and 1 conditional return values, but was called 2 time(s)"
+)]
+fn if_no_rule_matches_and_no_fallback_then_panics_when_called() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return_once("three".to_string());
+
+    use_test_trait(&spy, 3);
+    use_test_trait(&spy, 3);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' had 1 return values set and 1 conditional return values, but was called 2 time(s)"
+)]
+fn unmatched_rule_values_are_not_counted_as_calls_when_panicking() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return_once("three".to_string());
+    spy.function.returns.set(["other".to_string()]);
+
+    use_test_trait(&spy, 1);
+    use_test_trait(&spy, 2);
+}
+
+#[test]
+#[should_panic(expected = "function 'function' had 2 unused return values when dropped")]
+fn if_a_rule_return_is_not_used_then_panics_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return_times(2, "three".to_string());
+}
+
+#[test]
+fn if_an_always_rule_is_not_used_then_does_not_panic_when_dropped() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|id| *id == 3)
+        .then_return("three".to_string());
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "async")]
+
+use std::time::Duration;
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    async fn function(&self, argument: String) -> String;
+}
+
+async fn use_trait_with_timeout<T: MyTrait>(trait_object: &T) -> Option<String> {
+    tokio::time::timeout(
+        Duration::from_millis(10),
+        trait_object.function("argument".to_string()),
+    )
+    .await
+    .ok()
+}
+
+#[tokio::test]
+async fn controlled_call_waits_for_response() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    let task = tokio::task::spawn({
+        let spy = spy.clone();
+        async move { spy.function("argument".to_string()).await }
+    });
+
+    let call = controller.next_call().await;
+    assert_eq!("argument", call.arguments());
+    assert!(!task.is_finished());
+
+    call.respond("return value".to_string());
+
+    assert_eq!("return value", task.await.unwrap());
+    assert_eq!(["argument".to_string()], spy.function.arguments);
+}
+
+#[tokio::test]
+async fn controlled_call_without_response_times_out() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+
+    let call = controller.try_next_call().unwrap();
+    call.respond("too late".to_string());
+
+    assert_eq!(["argument".to_string()], spy.function.arguments);
+    spy.function.expect_calls(1);
+}
+
+#[tokio::test]
+async fn controlled_calls_can_be_responded_to_out_of_order() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    let first = tokio::task::spawn({
+        let spy = spy.clone();
+        async move { spy.function("first".to_string()).await }
+    });
+    let first_call = controller.next_call().await;
+
+    let second = tokio::task::spawn({
+        let spy = spy.clone();
+        async move { spy.function("second".to_string()).await }
+    });
+    let second_call = controller.next_call().await;
+
+    second_call.respond("second response".to_string());
+    assert_eq!("second response", second.await.unwrap());
+
+    first_call.respond("first response".to_string());
+    assert_eq!("first response", first.await.unwrap());
+
+    assert_eq!(
+        ["first".to_string(), "second".to_string()],
+        spy.function.arguments
+    );
+}
+
+#[tokio::test]
+async fn controlled_call_arguments_are_captured_when_called() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    let task = tokio::task::spawn({
+        let spy = spy.clone();
+        async move { spy.function("argument".to_string()).await }
+    });
+    let call = controller.next_call().await;
+
+    assert_eq!(["argument".to_string()], spy.function.arguments);
+
+    call.respond("return value".to_string());
+    assert_eq!("return value", task.await.unwrap());
+}
+
+#[tokio::test]
+async fn no_call_made_yet() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    assert!(controller.try_next_call().is_none());
+}
+
+#[tokio::test]
+async fn dropped_call_panics_caller() {
+    let spy = MyTraitSpy::default();
+    let controller = spy.function.returns.controlled();
+
+    let task = tokio::task::spawn({
+        let spy = spy.clone();
+        async move { spy.function("argument".to_string()).await }
+    });
+
+    drop(controller.next_call().await);
+
+    assert!(task.await.unwrap_err().is_panic());
+}
+
+#[tokio::test]
+async fn reset_removes_controller() {
+    let spy = MyTraitSpy::default();
+    let _controller = spy.function.returns.controlled();
+
+    spy.autospy_reset();
+    spy.function.returns.set(["return value".to_string()]);
+
+    assert_eq!(
+        Some("return value".to_string()),
+        use_trait_with_timeout(&spy).await
+    );
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "async")]
+
+use std::time::{Duration, Instant};
+
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait MyTrait: Send + Sync + 'static {
+    async fn function(&self, argument: String) -> String;
+}
+
+async fn use_trait_with_timeout<T: MyTrait>(trait_object: &T) -> Option<String> {
+    tokio::time::timeout(
+        Duration::from_millis(50),
+        trait_object.function("argument".to_string()),
+    )
+    .await
+    .ok()
+}
+
+#[tokio::test]
+async fn return_values_are_delayed_in_order() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_with_delay([
+        (Duration::from_millis(100), "slow".to_string()),
+        (Duration::ZERO, "fast".to_string()),
+    ]);
+
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+    assert_eq!(Some("fast".to_string()), use_trait_with_timeout(&spy).await);
+}
+
+#[tokio::test]
+async fn arguments_are_captured_before_delay() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| "return value".to_string());
+    spy.function.returns.delay_all(Duration::from_secs(1));
+
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+    assert_eq!(["argument".to_string()], spy.function.arguments);
+}
+
+#[tokio::test]
+async fn all_return_values_are_delayed() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| "return value".to_string());
+    spy.function.returns.delay_all(Duration::from_millis(50));
+
+    let start = Instant::now();
+    spy.function("argument".to_string()).await;
+    spy.function("argument".to_string()).await;
+
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn delays_from_set_with_delay_take_precedence() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.delay_all(Duration::from_secs(1));
+    spy.function
+        .returns
+        .set_with_delay([(Duration::ZERO, "return value".to_string())]);
+    spy.function
+        .returns
+        .set_fallback_fn(|_| "fallback".to_string());
+
+    assert_eq!(
+        Some("return value".to_string()),
+        use_trait_with_timeout(&spy).await
+    );
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+}
+
+#[tokio::test]
+async fn reset_removes_delays() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.delay_all(Duration::from_secs(1));
+
+    spy.autospy_reset();
+    spy.function.returns.set(["return value".to_string()]);
+
+    assert_eq!(
+        Some("return value".to_string()),
+        use_trait_with_timeout(&spy).await
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(start_paused = true)]
+async fn delays_respect_paused_time() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| "return value".to_string());
+    spy.function.returns.delay_all(Duration::from_secs(30));
+
+    let start = tokio::time::Instant::now();
+    spy.function("argument".to_string()).await;
+
+    assert!(start.elapsed() >= Duration::from_secs(30));
+}
+
+#[tokio::test]
+async fn delays_from_set_with_delay_are_not_used_by_rules() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|argument| argument == "ruled")
+        .then_return("ruled value".to_string());
+    spy.function
+        .returns
+        .set_with_delay([(Duration::from_secs(1), "slow".to_string())]);
+
+    assert_eq!(
+        "ruled value".to_string(),
+        spy.function("ruled".to_string()).await
+    );
+    assert_eq!(None, use_trait_with_timeout(&spy).await);
+}
+
+#[tokio::test]
+async fn delays_from_set_with_delay_are_not_used_by_fallbacks() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .set_fallback_fn(|_| "fallback".to_string());
+    spy.function
+        .returns
+        .set_with_delay([(Duration::from_millis(50), "slow".to_string())]);
+
+    let start = Instant::now();
+    assert_eq!(
+        "slow".to_string(),
+        spy.function("argument".to_string()).await
+    );
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    let start = Instant::now();
+    assert_eq!(
+        "fallback".to_string(),
+        spy.function("argument".to_string()).await
+    );
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
+
+#[test]
+fn delays_work_without_a_tokio_runtime() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| "return value".to_string());
+    spy.function.returns.delay_all(Duration::from_millis(50));
+
+    let start = Instant::now();
+    let first = spy.function("argument".to_string());
+    let second = spy.function("argument".to_string());
+    pollster::block_on(futures::future::join(first, second));
+
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(start.elapsed() < Duration::from_millis(100));
+}
@@ -0,0 +1,92 @@
+#[derive(Clone, PartialEq)]
+struct NotDebug;
+
+#[autospy::autospy(calls)]
+trait MyTrait {
+    fn function(&self, argument: u8);
+    fn multiple(&self, id: u8, name: &str);
+    #[allow(dead_code)]
+    fn never(&self);
+}
+
+#[autospy::autospy]
+trait MyNotDebugTrait {
+    fn not_debug(&self, argument: NotDebug);
+}
+
+#[autospy::autospy]
+trait MyRendererTrait {
+    fn render_interactions(&self) -> String;
+}
+
+fn use_trait(trait_object: &impl MyTrait) {
+    trait_object.function(1);
+    trait_object.multiple(2, "two");
+    trait_object.function(3);
+}
+
+fn called_spy() -> MyTraitSpy {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set_fn(|_| ());
+    spy.multiple.returns.set_fn(|_| ());
+
+    use_trait(&spy);
+
+    spy
+}
+
+#[test]
+fn render_interactions_lists_each_function_and_call_order() {
+    assert_eq!(
+        "function:
+  [0] 1
+  [1] 3
+multiple:
+  [0] (2, \"two\")
+never: not called
+calls:
+  [0] Function(1)
+  [1] Multiple((2, \"two\"))
+  [2] Function(3)",
+        called_spy().autospy_render_interactions()
+    );
+}
+
+#[test]
+fn render_interactions_counts_arguments_without_debug() {
+    let spy = MyNotDebugTraitSpy::default();
+    spy.not_debug.returns.set([()]);
+
+    spy.not_debug(NotDebug);
+
+    assert_eq!(
+        "not_debug: called 1 time(s), not Debug",
+        spy.autospy_render_interactions()
+    );
+}
+
+#[test]
+fn trait_function_named_render_interactions_is_not_shadowed() {
+    let spy = MyRendererTraitSpy::default();
+    spy.render_interactions
+        .returns
+        .set(["rendered".to_string()]);
+
+    assert_eq!("rendered", spy.render_interactions());
+    assert_eq!(
+        "render_interactions:\n  [0] ()",
+        spy.autospy_render_interactions()
+    );
+}
+
+#[cfg(feature = "insta")]
+#[test]
+fn interactions_snapshot() {
+    autospy::assert_interactions_snapshot!(called_spy());
+}
+
+#[cfg(feature = "insta")]
+#[test]
+fn named_interactions_snapshot() {
+    autospy::assert_interactions_snapshot!("named", MyTraitSpy::default());
+}
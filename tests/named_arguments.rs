@@ -0,0 +1,91 @@
+#[autospy::autospy(named_arguments, calls)]
+trait MyTrait {
+    fn send(&self, topic: &str, retries: u8);
+    fn flush(&self, timeout: u64);
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T) {
+    trait_object.send("orders", 3);
+    trait_object.flush(10);
+}
+
+#[test]
+fn arguments_are_captured_in_named_fields() {
+    let spy = MyTraitSpy::default();
+    spy.send.returns.set([()]);
+    spy.flush.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    let arguments = spy.send.arguments.take();
+    assert_eq!("orders", arguments[0].topic);
+    assert_eq!(3, arguments[0].retries);
+}
+
+#[test]
+fn single_arguments_are_not_wrapped() {
+    let spy = MyTraitSpy::default();
+    spy.send.returns.set([()]);
+    spy.flush.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!([10], spy.flush.arguments);
+}
+
+#[test]
+fn named_arguments_can_be_compared_with_tuples() {
+    let spy = MyTraitSpy::default();
+    spy.send.returns.set([()]);
+    spy.flush.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!([("orders".to_string(), 3)], spy.send.arguments);
+    assert_eq!(
+        MyTraitSpySendArgs {
+            topic: "orders".to_string(),
+            retries: 3
+        },
+        ("orders".to_string(), 3)
+    );
+}
+
+#[test]
+fn named_arguments_are_recorded_in_calls() {
+    let spy = MyTraitSpy::default();
+    spy.send.returns.set([()]);
+    spy.flush.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!(
+        [
+            MyTraitSpyCall::Send(MyTraitSpySendArgs {
+                topic: "orders".to_string(),
+                retries: 3
+            }),
+            MyTraitSpyCall::Flush(10)
+        ],
+        spy.autospy_calls()
+    );
+}
+
+struct Payload(u8);
+
+#[autospy::autospy(named_arguments)]
+trait MyPayloadTrait {
+    fn send(&self, topic: &str, payload: Payload);
+}
+
+#[test]
+fn arguments_without_derives_are_captured_in_named_fields() {
+    let spy = MyPayloadTraitSpy::default();
+    spy.send.returns.set([()]);
+
+    spy.send("orders", Payload(1));
+
+    let arguments = spy.send.arguments.take();
+    assert_eq!("orders", arguments[0].topic);
+    assert_eq!(1, arguments[0].payload.0);
+}
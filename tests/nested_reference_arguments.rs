@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+#[autospy::autospy]
+trait MyTrait {
+    fn option(&self, argument: Option<&str>);
+    fn slice(&self, argument: &[&str]);
+    fn tuple(&self, argument: (&Path, &str, u8));
+    fn vec(&self, argument: Vec<&u8>);
+    fn array(&self, argument: [&str; 2]);
+    fn nested(&self, argument: &[Option<Vec<&&str>>]);
+}
+
+#[test]
+fn option_of_reference_is_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.option.returns.set([(), ()]);
+
+    spy.option(Some("hello"));
+    spy.option(None);
+
+    assert_eq!([Some("hello".to_string()), None], spy.option.arguments);
+}
+
+#[test]
+fn slice_of_references_is_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.slice.returns.set([()]);
+
+    spy.slice(&["hello", "world"]);
+
+    assert_eq!([vec!["hello", "world"]], spy.slice.arguments);
+}
+
+#[test]
+fn tuple_of_references_is_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.tuple.returns.set([()]);
+
+    spy.tuple((Path::new("/tmp"), "hello", 1));
+
+    assert_eq!(
+        [(PathBuf::from("/tmp"), "hello".to_string(), 1)],
+        spy.tuple.arguments
+    );
+}
+
+#[test]
+fn vec_of_references_is_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.vec.returns.set([()]);
+
+    spy.vec(vec![&1, &2]);
+
+    assert_eq!([vec![1, 2]], spy.vec.arguments);
+}
+
+#[test]
+fn array_of_references_is_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.array.returns.set([()]);
+
+    spy.array(["hello", "world"]);
+
+    assert_eq!([["hello", "world"]], spy.array.arguments);
+}
+
+#[test]
+fn nested_references_are_captured_as_owned() {
+    let spy = MyTraitSpy::default();
+    spy.nested.returns.set([()]);
+
+    spy.nested(&[Some(vec![&"hello"])]);
+
+    assert_eq!(
+        [vec![Some(vec!["hello".to_string()])]],
+        spy.nested.arguments
+    );
+}
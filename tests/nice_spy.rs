@@ -0,0 +1,146 @@
+#[autospy::autospy(nice)]
+trait MyTrait {
+    fn count(&self, name: &str) -> usize;
+    fn name(&self) -> String;
+    #[autospy(default_return = "Ok(())")]
+    fn flush(&self) -> Result<(), String>;
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T) -> (usize, String, Result<(), String>) {
+    (
+        trait_object.count("requests"),
+        trait_object.name(),
+        trait_object.flush(),
+    )
+}
+
+#[test]
+fn unconfigured_functions_return_default_or_declared_values() {
+    let spy = MyTraitSpy::default();
+
+    assert_eq!((0, String::new(), Ok(())), use_test_trait(&spy));
+}
+
+#[test]
+fn arguments_are_captured_when_using_fallback() {
+    let spy = MyTraitSpy::default();
+
+    assert_eq!(0, spy.count("requests"));
+
+    assert_eq!(["requests"], spy.count.arguments);
+}
+
+#[test]
+fn set_values_are_used_before_fallback() {
+    let spy = MyTraitSpy::default();
+    spy.count.returns.set([5]);
+    spy.flush.returns.set([Err("full".to_string())]);
+
+    assert_eq!(
+        (5, String::new(), Err("full".to_string())),
+        use_test_trait(&spy)
+    );
+    assert_eq!((0, String::new(), Ok(())), use_test_trait(&spy));
+}
+
+#[test]
+fn fallback_is_kept_after_reset() {
+    let spy = MyTraitSpy::default();
+    spy.count.returns.set([5]);
+
+    spy.autospy_reset();
+
+    assert_eq!((0, String::new(), Ok(())), use_test_trait(&spy));
+}
+
+#[autospy::autospy]
+trait MyStrictTrait {
+    fn count(&self) -> usize;
+    #[autospy(default_return = "10")]
+    fn size(&self) -> usize;
+}
+
+#[test]
+fn default_return_attribute_without_nice_trait() {
+    let spy = MyStrictTraitSpy::default();
+
+    assert_eq!(10, spy.size());
+}
+
+#[test]
+#[should_panic(expected = "function 'count' had 0 return values set, but was called 1 time(s)")]
+fn functions_without_default_return_attribute_still_panic() {
+    let spy = MyStrictTraitSpy::default();
+
+    spy.count();
+}
+
+#[test]
+fn use_default_at_runtime() {
+    let spy = MyStrictTraitSpy::default();
+    spy.count.returns.use_default();
+
+    assert_eq!(0, spy.count());
+}
+
+#[test]
+fn fallback_fn_uses_the_arguments() {
+    let spy = MyTraitSpy::default();
+    spy.count.returns.set_fallback_fn(String::len);
+
+    assert_eq!(8, spy.count("requests"));
+}
+
+#[autospy::autospy(nice, wrapping)]
+trait MyWrappedTrait: Send + Sync {
+    fn count(&self) -> usize;
+    #[autospy(default_return = "Ok(1)")]
+    fn size(&self) -> Result<usize, String>;
+}
+
+struct RealWrapped;
+
+impl MyWrappedTrait for RealWrapped {
+    fn count(&self) -> usize {
+        7
+    }
+
+    fn size(&self) -> Result<usize, String> {
+        Ok(8)
+    }
+}
+
+#[test]
+fn wrapping_forwards_before_the_fallback() {
+    let spy = MyWrappedTraitSpy::wrapping(RealWrapped);
+    spy.count.returns.set([5]);
+
+    assert_eq!(5, spy.count());
+    assert_eq!(7, spy.count());
+    assert_eq!(Ok(8), spy.size());
+}
+
+#[test]
+fn fallback_is_used_without_inner_implementation() {
+    let spy = MyWrappedTraitSpy::default();
+
+    assert_eq!(0, spy.count());
+    assert_eq!(Ok(1), spy.size());
+}
+
+#[autospy::autospy(nice)]
+trait MyRecordDefaultTrait {
+    fn count(&self) -> usize;
+    #[autospy(record_default)]
+    fn checked_count(&self) -> Result<usize, String> {
+        Ok(self.count())
+    }
+}
+
+#[test]
+fn recorded_default_functions_do_not_need_default_return_type() {
+    let spy = MyRecordDefaultTraitSpy::default();
+
+    assert_eq!(Ok(0), spy.checked_count());
+    assert_eq!([()], spy.checked_count.arguments);
+}
@@ -0,0 +1,137 @@
+use std::io::Read;
+
+#[autospy::autospy(external)]
+trait Read {
+    fn read(&mut self, #[autospy(out)] buf: &mut [u8]) -> std::io::Result<usize>;
+}
+
+#[autospy::autospy(calls)]
+trait MyTrait {
+    fn fill(
+        &self,
+        count: usize,
+        #[autospy(out)]
+        #[autospy(capture_written)]
+        output: &mut Vec<u8>,
+    ) -> bool;
+    fn append(&self, #[autospy(out)] output: &mut Vec<u8>);
+    fn swap(
+        &self,
+        #[autospy(out)] left: &mut u8,
+        #[autospy(ignore)]
+        #[autospy(out)]
+        right: &mut u8,
+    );
+}
+
+fn read_all(reader: &mut impl Read) -> Vec<u8> {
+    let mut contents = Vec::new();
+    let mut buffer = [0; 4];
+    loop {
+        let read = reader.read(&mut buffer).unwrap();
+        if read == 0 {
+            return contents;
+        }
+        contents.extend_from_slice(&buffer[..read]);
+    }
+}
+
+#[test]
+fn return_function_can_write_into_out_arguments() {
+    let mut spy = ReadSpy::default();
+    let mut chunks = vec![b"ipsum".as_slice(), b"lorem ".as_slice()];
+    let mut remainder: Vec<u8> = Vec::new();
+    spy.read.returns.set_fn_mut(move |_, buf| {
+        if remainder.is_empty()
+            && let Some(chunk) = chunks.pop()
+        {
+            remainder = chunk.to_vec();
+        }
+        let read = remainder.len().min(buf.len());
+        buf[..read].copy_from_slice(&remainder[..read]);
+        remainder.drain(..read);
+        Ok(read)
+    });
+
+    assert_eq!(b"lorem ipsum".to_vec(), read_all(&mut spy));
+}
+
+#[test]
+fn out_arguments_are_captured_as_passed_in() {
+    let spy = MyTraitSpy::default();
+    spy.append.returns.set_fn_mut(|_, output| output.push(7));
+
+    let mut output = vec![1];
+    spy.append(&mut output);
+
+    assert_eq!(vec![1, 7], output);
+    assert_eq!([vec![1]], spy.append.arguments);
+}
+
+#[test]
+fn out_arguments_marked_with_capture_written_are_captured_after_being_written() {
+    let spy = MyTraitSpy::default();
+    spy.fill.returns.set_fn_mut(|(count, _), output| {
+        output.resize(*count, 7);
+        true
+    });
+
+    let mut output = vec![1];
+
+    assert!(spy.fill(3, &mut output));
+    assert_eq!(vec![1, 7, 7], output);
+    assert_eq!([(3, vec![1, 7, 7])], spy.fill.arguments);
+    assert_eq!(
+        [MyTraitSpyCall::Fill((3, vec![1, 7, 7]))],
+        spy.autospy_calls()
+    );
+}
+
+#[test]
+fn return_function_receives_arguments_captured_before_the_call() {
+    let spy = MyTraitSpy::default();
+    spy.fill.returns.set_fn_mut(|(count, before), output| {
+        assert_eq!(vec![1], *before);
+        output.resize(*count, 7);
+        true
+    });
+
+    assert!(spy.fill(2, &mut vec![1]));
+}
+
+#[test]
+fn ignored_out_arguments_can_still_be_written() {
+    let spy = MyTraitSpy::default();
+    spy.swap
+        .returns
+        .set_fn_mut(|_, left, right| std::mem::swap(left, right));
+
+    let (mut left, mut right) = (1, 2);
+    spy.swap(&mut left, &mut right);
+
+    assert_eq!((2, 1), (left, right));
+    assert_eq!([1], spy.swap.arguments);
+}
+
+#[test]
+fn other_return_values_are_used_without_return_function() {
+    let spy = MyTraitSpy::default();
+    spy.fill.returns.set([false]);
+
+    let mut output = vec![1];
+
+    assert!(!spy.fill(3, &mut output));
+    assert_eq!(vec![1], output);
+    assert_eq!([(3, vec![1])], spy.fill.arguments);
+}
+
+#[test]
+fn return_function_is_cleared_by_reset() {
+    let spy = MyTraitSpy::default();
+    spy.fill.returns.set_fn_mut(|_, _| true);
+
+    spy.autospy_reset();
+    spy.fill.returns.set([false]);
+
+    assert!(!spy.fill(3, &mut Vec::new()));
+}
@@ -0,0 +1,49 @@
+#[autospy::autospy]
+#[async_trait::async_trait]
+trait TestTrait: Send + Sync {
+    fn lookup(&self, key: &str) -> Option<u32>;
+    #[autospy(record_default)]
+    fn lookup_or(&self, key: &str, fallback: u32) -> u32 {
+        self.lookup(key).unwrap_or(fallback)
+    }
+    #[autospy(record_default)]
+    async fn lookup_async(&self, key: &str) -> Option<u32> {
+        self.lookup(key)
+    }
+    #[autospy(record_default)]
+    fn reset_counter(&mut self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn function_marked_with_record_default_captures_arguments_and_calls_default() {
+    let spy = TestTraitSpy::default();
+    spy.lookup.returns.set([None]);
+
+    assert_eq!(7, spy.lookup_or("key", 7));
+
+    assert_eq!([("key".to_string(), 7)], spy.lookup_or.arguments);
+    assert_eq!(["key"], spy.lookup.arguments);
+}
+
+#[test]
+fn async_function_marked_with_record_default_captures_arguments_and_calls_default() {
+    let spy = TestTraitSpy::default();
+    spy.lookup.returns.set([Some(1)]);
+
+    assert_eq!(Some(1), pollster::block_on(spy.lookup_async("key")));
+
+    assert_eq!(["key"], spy.lookup_async.arguments);
+    assert_eq!(["key"], spy.lookup.arguments);
+}
+
+#[test]
+fn mutable_function_marked_with_record_default_is_counted() {
+    let mut spy = TestTraitSpy::default();
+
+    assert_eq!(0, spy.reset_counter());
+
+    spy.reset_counter.expect_calls(1);
+    spy.autospy_checkpoint();
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "serde")]
+
+use std::path::PathBuf;
+
+#[autospy::autospy(recording)]
+#[async_trait::async_trait]
+trait Gateway: Send + Sync {
+    fn fetch(&self, id: u32) -> Option<String>;
+    fn store(&self, id: u32, name: &str) -> bool;
+    async fn count(&self) -> usize;
+}
+
+struct RealGateway;
+
+#[async_trait::async_trait]
+impl Gateway for RealGateway {
+    fn fetch(&self, id: u32) -> Option<String> {
+        (id == 1).then(|| "alice".to_string())
+    }
+
+    fn store(&self, _id: u32, _name: &str) -> bool {
+        true
+    }
+
+    async fn count(&self) -> usize {
+        1
+    }
+}
+
+fn use_gateway(gateway: &impl Gateway, id: u32) -> Option<String> {
+    gateway.store(id, "bob");
+    gateway.fetch(id)
+}
+
+fn recording_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join("autospy_recording_tests")
+        .join(format!("{name}.json"))
+}
+
+fn record(name: &str) -> PathBuf {
+    let path = recording_path(name);
+    let spy = GatewaySpy::recording(RealGateway);
+
+    assert_eq!(Some("alice".to_string()), use_gateway(&spy, 1));
+    assert_eq!(1, pollster::block_on(spy.count()));
+
+    spy.save_recording(&path);
+    path
+}
+
+#[test]
+fn recording_saves_forwarded_arguments_and_returns() {
+    let path = record("saves_forwarded");
+
+    let recording: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+    assert_eq!(
+        serde_json::json!({
+            "count": [{ "arguments": null, "returns": 1 }],
+            "fetch": [{ "arguments": 1, "returns": "alice" }],
+            "store": [{ "arguments": [1, "bob"], "returns": true }],
+        }),
+        recording
+    );
+}
+
+#[test]
+fn calls_with_returns_set_are_not_recorded() {
+    let path = recording_path("returns_set");
+    let spy = GatewaySpy::recording(RealGateway);
+    spy.fetch.returns.set([None]);
+
+    assert_eq!(None, spy.fetch(1));
+    assert_eq!(Some("alice".to_string()), spy.fetch(1));
+
+    spy.save_recording(&path);
+    let recording: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+    assert_eq!(
+        serde_json::json!([{ "arguments": 1, "returns": "alice" }]),
+        recording["fetch"]
+    );
+}
+
+#[test]
+fn replayed_returns_are_returned_in_order() {
+    let path = record("replay_in_order");
+
+    let spy = GatewaySpy::default();
+    spy.fetch.returns.replay_from(&path);
+    spy.store.returns.replay_from(&path);
+    spy.count.returns.replay_from(&path);
+
+    assert_eq!(Some("alice".to_string()), use_gateway(&spy, 1));
+    assert_eq!(1, pollster::block_on(spy.count()));
+    assert_eq!([(1, "bob".to_string())], spy.store.arguments);
+}
+
+#[test]
+#[should_panic(expected = "(- recorded, + actual):
+  -[0] 1
+  +[0] 2")]
+fn replay_panics_when_arguments_diverge() {
+    let path = record("replay_diverges");
+
+    let spy = GatewaySpy::default();
+    spy.fetch.returns.replay_from(&path);
+    spy.store.returns.set_fn(|_| true);
+
+    use_gateway(&spy, 2);
+}
+
+#[test]
+#[should_panic(expected = "function 'fetch' had 1 unused return values when dropped")]
+fn replay_panics_when_recorded_calls_are_unused() {
+    let path = record("replay_unused");
+
+    let spy = GatewaySpy::default();
+    spy.fetch.returns.replay_from(&path);
+}
+
+#[test]
+#[should_panic(expected = "has no interactions for function 'fetch'")]
+fn replay_panics_when_function_is_not_recorded() {
+    let path = recording_path("replay_missing");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "{}").unwrap();
+
+    let spy = GatewaySpy::default();
+    spy.fetch.returns.replay_from(&path);
+}
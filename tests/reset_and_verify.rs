@@ -0,0 +1,158 @@
+#[autospy::autospy]
+trait MyTrait: Supertrait {
+    fn function(&self, id: u8) -> u8;
+    autospy::supertrait! {
+        trait Supertrait {
+            fn super_function(&self);
+        }
+    }
+}
+
+trait Supertrait {
+    fn super_function(&self);
+}
+
+fn use_test_trait<T: MyTrait>(trait_object: &T) -> u8 {
+    trait_object.super_function();
+    trait_object.function(1)
+}
+
+#[test]
+fn reset_clears_arguments_and_return_values_of_all_functions() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1, 2]);
+    spy.super_function.returns.set([(), ()]);
+
+    use_test_trait(&spy);
+
+    spy.autospy_reset();
+
+    assert!(spy.function.arguments.is_empty());
+    assert!(spy.super_function.arguments.is_empty());
+}
+
+#[test]
+fn spy_can_be_reused_after_reset() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+    spy.super_function.returns.set([()]);
+
+    assert_eq!(1, use_test_trait(&spy));
+
+    spy.autospy_reset();
+    spy.function.returns.set([2]);
+    spy.super_function.returns.set([()]);
+
+    assert_eq!(2, use_test_trait(&spy));
+    assert_eq!([1], spy.function.arguments);
+}
+
+#[test]
+#[should_panic(expected = "function 'function' had 2 return values set, but was called 3 time(s)")]
+fn reset_clears_the_set_count() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+
+    spy.autospy_reset();
+    spy.function.returns.set([1, 2]);
+
+    spy.function(1);
+    spy.function(1);
+    spy.function(1);
+}
+
+#[test]
+fn reset_clears_call_count_expectations() {
+    let spy = MyTraitSpy::default();
+    spy.function.expect_never();
+
+    spy.autospy_reset();
+    spy.function.returns.set([1]);
+
+    spy.function(1);
+}
+
+#[test]
+fn checkpoint_passes_when_all_return_values_used() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+    spy.super_function.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    spy.autospy_checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "function 'super_function' had 1 unused return values at checkpoint")]
+fn checkpoint_panics_if_supertrait_function_return_values_unused() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+    spy.super_function.returns.set([(), ()]);
+
+    use_test_trait(&spy);
+
+    spy.autospy_checkpoint();
+}
+
+#[test]
+fn assert_no_more_interactions_passes_when_arguments_taken() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+    spy.super_function.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!(vec![1], spy.function.arguments.take());
+    assert_eq!(vec![()], spy.super_function.arguments.take());
+    spy.autospy_assert_no_more_interactions();
+}
+
+#[test]
+#[should_panic(expected = "function 'function' had 1 interaction(s) that were not taken")]
+fn assert_no_more_interactions_panics_when_arguments_not_taken() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([1]);
+    spy.super_function.returns.set([()]);
+
+    use_test_trait(&spy);
+
+    assert_eq!(vec![()], spy.super_function.arguments.take());
+    spy.autospy_assert_no_more_interactions();
+}
+
+#[autospy::autospy(calls)]
+trait MyCallsTrait {
+    fn function(&self);
+}
+
+#[test]
+fn reset_clears_recorded_calls() {
+    let spy = MyCallsTraitSpy::default();
+    spy.function.returns.set([()]);
+
+    spy.function();
+    spy.autospy_reset();
+
+    assert!(spy.autospy_calls().get().is_empty());
+}
+
+#[autospy::autospy]
+trait Timer {
+    fn reset(&self) -> u8;
+    fn checkpoint(&self) -> u8;
+    fn assert_no_more_interactions(&self) -> u8;
+}
+
+#[test]
+fn trait_functions_named_like_spy_methods_are_not_shadowed() {
+    let spy = TimerSpy::default();
+    spy.reset.returns.set([1]);
+    spy.checkpoint.returns.set([2]);
+    spy.assert_no_more_interactions.returns.set([3]);
+
+    assert_eq!(1, spy.reset());
+    assert_eq!(2, spy.checkpoint());
+    assert_eq!(3, spy.assert_no_more_interactions());
+    assert_eq!([()], spy.reset.arguments);
+}
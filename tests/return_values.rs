@@ -99,6 +99,7 @@ fn calling_set_overrides_set_fn_and_vice_versa() {
     assert_eq!(use_test_trait(&spy, 0), 0);
 }
 
+// the first line of the panic, the details that follow are covered in tests/rich_panics.rs
 fn panic_message<F, R>(function: F) -> Option<String>
 where
     F: FnOnce() -> R + std::panic::UnwindSafe,
@@ -108,6 +109,7 @@ where
         .and_then(|boxed_any| {
             boxed_any
                 .downcast_ref::<String>()
+                .and_then(|message| message.lines().next())
                 .map(std::string::ToString::to_string)
         })
 }
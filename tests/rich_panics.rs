@@ -0,0 +1,149 @@
+use std::panic::Location;
+
+#[derive(Clone, PartialEq)]
+struct NotDebug;
+
+#[autospy::autospy]
+trait MyTrait: Supertrait {
+    fn function(&self, argument: u8) -> u8;
+    fn not_debug(&self, argument: NotDebug) -> NotDebug;
+    autospy::supertrait! {
+        trait Supertrait {
+            fn super_function(&self) -> String;
+        }
+    }
+}
+
+trait Supertrait {
+    #[allow(dead_code)]
+    fn super_function(&self) -> String;
+}
+
+#[track_caller]
+fn set_returns<const N: usize>(spy: &MyTraitSpy, values: [u8; N]) -> &'static Location<'static> {
+    spy.function.returns.set(values);
+    Location::caller()
+}
+
+#[test]
+fn called_too_many_times_panic_lists_captured_arguments_and_set_location() {
+    let spy = MyTraitSpy::default();
+    let set_at = set_returns(&spy, [10]);
+
+    spy.function(1);
+
+    assert_eq!(
+        Some(format!(
+            "function 'function' had 1 return values set, but was called 2 time(s)
+  trait: MyTrait
+  return values set at: {set_at}
+  captured arguments: [1, 2]"
+        )),
+        panic_message(|| spy.function(2))
+    );
+}
+
+#[test]
+#[should_panic(expected = "return values set at: tests/rich_panics.rs:")]
+fn set_location_is_in_the_test_file() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([10]);
+}
+
+#[test]
+fn set_location_is_the_latest_set() {
+    let spy = MyTraitSpy::default();
+    set_returns(&spy, [10]);
+    spy.function(1);
+    let set_at = set_returns(&spy, [20]);
+    spy.function(2);
+    let _ = spy.function.arguments.take();
+
+    assert_eq!(
+        Some(format!(
+            "function 'function' had 2 return values set, but was called 3 time(s)
+  trait: MyTrait
+  return values set at: {set_at}
+  captured arguments: [3]"
+        )),
+        panic_message(|| spy.function(3))
+    );
+}
+
+#[test]
+fn unused_return_values_panic_lists_unused_return_values() {
+    let spy = MyTraitSpy::default();
+    let set_at = set_returns(&spy, [10, 20, 30]);
+
+    spy.function(1);
+
+    assert_eq!(
+        Some(format!(
+            "function 'function' had 2 unused return values when dropped
+  trait: MyTrait
+  return values set at: {set_at}
+  unused return values: [20, 30]
+  captured arguments: [1]"
+        )),
+        panic_message(|| drop(spy))
+    );
+}
+
+#[test]
+#[should_panic(expected = "unused return values: [5]")]
+fn unused_return_values_include_conditional_return_values() {
+    let spy = MyTraitSpy::default();
+    spy.function
+        .returns
+        .when(|argument| *argument == 1)
+        .then_return_once(5);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'super_function' had 1 unused return values at checkpoint
+  trait: Supertrait"
+)]
+fn supertrait_function_panic_names_supertrait() {
+    let spy = MyTraitSpy::default();
+    spy.super_function.returns.set(["value".to_string()]);
+
+    spy.autospy_checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "unused return values: 1 value(s), not Debug
+  captured arguments: 1 value(s), not Debug")]
+fn types_without_debug_are_counted() {
+    let spy = MyTraitSpy::default();
+    spy.not_debug.returns.set([NotDebug, NotDebug]);
+
+    spy.not_debug(NotDebug);
+}
+
+#[test]
+#[should_panic(
+    expected = "function 'function' had 0 return values set, but was called 1 time(s)
+  trait: MyTrait
+  captured arguments: [1]"
+)]
+fn set_location_is_cleared_on_reset() {
+    let spy = MyTraitSpy::default();
+    spy.function.returns.set([10]);
+    spy.autospy_reset();
+
+    spy.function(1);
+}
+
+fn panic_message<F, R>(function: F) -> Option<String>
+where
+    F: FnOnce() -> R + std::panic::UnwindSafe,
+{
+    std::panic::catch_unwind(function)
+        .err()
+        .and_then(|boxed_any| {
+            boxed_any
+                .downcast_ref::<String>()
+                .map(std::string::ToString::to_string)
+        })
+}
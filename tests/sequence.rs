@@ -0,0 +1,142 @@
+#[autospy::autospy]
+trait Store {
+    fn commit(&self, id: u8);
+}
+
+#[autospy::autospy]
+trait Publisher {
+    fn send(&self, message: &str);
+}
+
+fn use_test_traits<S: Store, P: Publisher>(store: &S, publisher: &P) {
+    store.commit(1);
+    publisher.send("committed");
+}
+
+#[test]
+fn calls_across_spies_are_recorded_in_order() {
+    let sequence = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.in_sequence(&sequence);
+    store.commit.returns.set([()]);
+
+    let publisher = PublisherSpy::default();
+    publisher.send.in_sequence(&sequence);
+    publisher.send.returns.set([()]);
+
+    use_test_traits(&store, &publisher);
+
+    assert_eq!(
+        vec!["Store::commit", "Publisher::send"],
+        sequence.interactions()
+    );
+    sequence.assert_order(["Store::commit", "Publisher::send"]);
+}
+
+#[test]
+fn functions_not_in_sequence_are_not_recorded() {
+    let sequence = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.returns.set([()]);
+
+    let publisher = PublisherSpy::default();
+    publisher.send.in_sequence(&sequence);
+    publisher.send.returns.set([()]);
+
+    use_test_traits(&store, &publisher);
+
+    sequence.assert_order(["Publisher::send"]);
+}
+
+#[test]
+fn clones_of_a_spy_record_into_the_same_sequence() {
+    let sequence = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.in_sequence(&sequence);
+    store.commit.returns.set([(), ()]);
+
+    let store_clone = store.clone();
+    store_clone.commit(1);
+    drop(store_clone);
+    store.commit(2);
+
+    sequence.assert_order(["Store::commit", "Store::commit"]);
+}
+
+#[test]
+fn function_can_be_in_multiple_sequences() {
+    let first = autospy::Sequence::default();
+    let second = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.in_sequence(&first);
+    store.commit.in_sequence(&second);
+    store.commit.returns.set([()]);
+
+    store.commit(1);
+
+    first.assert_order(["Store::commit"]);
+    second.assert_order(["Store::commit"]);
+}
+
+#[test]
+fn assert_order_reports_expected_and_actual_order() {
+    let sequence = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.in_sequence(&sequence);
+    store.commit.returns.set([()]);
+
+    let publisher = PublisherSpy::default();
+    publisher.send.in_sequence(&sequence);
+    publisher.send.returns.set([()]);
+
+    use_test_traits(&store, &publisher);
+
+    assert_eq!(
+        Some(
+            "sequence did not match the expected order\nexpected: [\"Publisher::send\", \"Store::commit\"]\n  actual: [\"Store::commit\", \"Publisher::send\"]"
+                .to_string()
+        ),
+        panic_message(|| sequence.assert_order(["Publisher::send", "Store::commit"]))
+    );
+}
+
+#[autospy::autospy]
+trait Cache {
+    fn commit(&self, id: u8);
+}
+
+#[test]
+fn functions_with_the_same_name_are_recorded_by_trait() {
+    let sequence = autospy::Sequence::default();
+
+    let store = StoreSpy::default();
+    store.commit.in_sequence(&sequence);
+    store.commit.returns.set([()]);
+
+    let cache = CacheSpy::default();
+    cache.commit.in_sequence(&sequence);
+    cache.commit.returns.set([()]);
+
+    cache.commit(1);
+    store.commit(1);
+
+    sequence.assert_order(["Cache::commit", "Store::commit"]);
+}
+
+fn panic_message<F, R>(function: F) -> Option<String>
+where
+    F: FnOnce() -> R + std::panic::UnwindSafe,
+{
+    std::panic::catch_unwind(function)
+        .err()
+        .and_then(|boxed_any| {
+            boxed_any
+                .downcast_ref::<String>()
+                .map(std::string::ToString::to_string)
+        })
+}
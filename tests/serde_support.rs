@@ -0,0 +1,118 @@
+#![cfg(feature = "serde")]
+
+use std::path::PathBuf;
+
+#[autospy::autospy(serialize)]
+trait Repository {
+    fn load(&self, id: u32) -> Option<(u32, String)>;
+    fn save(&self, id: u32, name: &str) -> bool;
+    #[allow(dead_code)]
+    fn clear(&self);
+}
+
+#[autospy::autospy(serialize)]
+trait GenericRepository<T> {
+    fn store(&self, value: T);
+}
+
+fn fixture_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join("autospy_serde_tests")
+        .join(format!("{name}.json"))
+}
+
+fn write_fixture(name: &str, json: &str) -> PathBuf {
+    let path = fixture_path(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, json).unwrap();
+    path
+}
+
+#[test]
+fn captured_arguments_serialize_as_a_sequence() {
+    let spy = RepositorySpy::default();
+    spy.load.returns.set([None, None]);
+
+    spy.load(1);
+    spy.load(2);
+
+    assert_eq!(
+        serde_json::json!([1, 2]),
+        serde_json::to_value(&spy.load.arguments).unwrap()
+    );
+}
+
+#[test]
+fn spy_serializes_arguments_of_each_function() {
+    let spy = RepositorySpy::default();
+    spy.load.returns.set([None]);
+    spy.save.returns.set([true]);
+
+    spy.load(1);
+    spy.save(1, "alice");
+
+    assert_eq!(
+        serde_json::json!({
+            "load": [1],
+            "save": [[1, "alice"]],
+            "clear": [],
+        }),
+        serde_json::to_value(&spy).unwrap()
+    );
+}
+
+#[test]
+fn generic_spy_serializes_arguments() {
+    let spy = GenericRepositorySpy::<String>::default();
+    spy.store.returns.set([()]);
+
+    spy.store("value".to_string());
+
+    assert_eq!(
+        serde_json::json!({ "store": ["value"] }),
+        serde_json::to_value(&spy).unwrap()
+    );
+}
+
+#[test]
+fn returns_are_set_from_json_in_order() {
+    let spy = RepositorySpy::default();
+    spy.load.returns.set_from_json(r#"[[1, "alice"], null]"#);
+
+    assert_eq!(Some((1, "alice".to_string())), spy.load(1));
+    assert_eq!(None, spy.load(2));
+}
+
+#[test]
+fn returns_are_set_from_file_in_order() {
+    let path = write_fixture("set_from_file", "[true, false]");
+
+    let spy = RepositorySpy::default();
+    spy.save.returns.set_from_file(&path);
+
+    assert!(spy.save(1, "alice"));
+    assert!(!spy.save(2, "bob"));
+}
+
+#[test]
+#[should_panic(expected = "function 'load' had 1 unused return values when dropped")]
+fn unused_returns_from_json_panic_when_dropped() {
+    let spy = RepositorySpy::default();
+    spy.load.returns.set_from_json("[null]");
+}
+
+#[test]
+#[should_panic(expected = "could not parse return values: ")]
+fn invalid_json_returns_panic() {
+    let spy = RepositorySpy::default();
+    spy.save.returns.set_from_json(r#"["not a bool"]"#);
+}
+
+#[test]
+#[should_panic(expected = "could not read return values '")]
+fn missing_fixture_file_panics() {
+    let spy = RepositorySpy::default();
+    spy.save
+        .returns
+        .set_from_file(fixture_path("does_not_exist"));
+}
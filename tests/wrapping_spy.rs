@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[autospy::autospy(wrapping)]
+#[async_trait::async_trait]
+trait SaveFile: Send + Sync {
+    fn save(&self, path: &str, contents: String) -> bool;
+    fn load(&self, path: &str) -> Option<String>;
+    async fn size(&self, path: &str) -> usize;
+    fn log(&self, #[autospy(ignore)] message: &str);
+}
+
+#[derive(Default)]
+struct InMemory {
+    files: Mutex<HashMap<String, String>>,
+}
+
+#[async_trait::async_trait]
+impl SaveFile for InMemory {
+    fn save(&self, path: &str, contents: String) -> bool {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), contents)
+            .is_none()
+    }
+
+    fn load(&self, path: &str) -> Option<String> {
+        self.files.lock().unwrap().get(path).cloned()
+    }
+
+    async fn size(&self, path: &str) -> usize {
+        self.load(path).map_or(0, |contents| contents.len())
+    }
+
+    fn log(&self, message: &str) {
+        self.save("log", message.to_string());
+    }
+}
+
+#[test]
+fn functions_without_returns_forward_to_inner() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+
+    assert!(spy.save("a.txt", "hello".to_string()));
+    assert_eq!(Some("hello".to_string()), spy.load("a.txt"));
+
+    assert_eq!(
+        [("a.txt".to_string(), "hello".to_string())],
+        spy.save.arguments
+    );
+    assert_eq!(["a.txt"], spy.load.arguments);
+}
+
+#[test]
+fn functions_with_returns_set_do_not_forward() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+    spy.load.returns.set([Some("overridden".to_string())]);
+
+    spy.save("a.txt", "hello".to_string());
+
+    assert_eq!(Some("overridden".to_string()), spy.load("a.txt"));
+    assert_eq!(Some("hello".to_string()), spy.load("a.txt"));
+}
+
+#[test]
+fn functions_with_returns_fn_do_not_forward() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+    spy.save.returns.set_fn(|_| false);
+
+    assert!(!spy.save("a.txt", "hello".to_string()));
+    assert_eq!(None, spy.load("a.txt"));
+}
+
+#[test]
+fn async_functions_forward_to_inner() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+
+    spy.save("a.txt", "hello".to_string());
+
+    assert_eq!(5, pollster::block_on(spy.size("a.txt")));
+    assert_eq!(["a.txt"], spy.size.arguments);
+}
+
+#[test]
+fn async_functions_with_returns_set_do_not_forward() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+    spy.size.returns.set([10]);
+
+    assert_eq!(10, pollster::block_on(spy.size("a.txt")));
+}
+
+#[test]
+fn ignored_arguments_are_forwarded_but_not_captured() {
+    let spy = SaveFileSpy::wrapping(InMemory::default());
+
+    spy.log("started");
+
+    assert_eq!(Some("started".to_string()), spy.load("log"));
+    assert_eq!(1, spy.log.arguments.len());
+}
+
+#[test]
+#[should_panic(expected = "function 'load' had 0 return values set, but was called 1 time(s)")]
+fn default_spy_does_not_forward() {
+    let spy = SaveFileSpy::default();
+
+    spy.load("a.txt");
+}